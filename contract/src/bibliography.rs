@@ -0,0 +1,120 @@
+use crate::*;
+
+const MAX_KEYWORDS: usize = 10;
+const MAX_FUNDING: usize = 10;
+const MAX_LABEL_LEN: usize = 64;    // keywords, field, language and license
+
+impl Contract {
+    pub(crate) fn check_bibliography(bibliography: &Bibliography, authors: usize) {
+        assert!(bibliography.keywords.len() <= MAX_KEYWORDS,"At most {} keywords!",MAX_KEYWORDS);
+        for keyword in bibliography.keywords.iter() {
+            assert!(!keyword.is_empty() && keyword.len() <= MAX_LABEL_LEN,"Invalid keyword {}",keyword);
+        }
+        for label in [&bibliography.field,&bibliography.language,&bibliography.license].iter().copied().flatten() {
+            assert!(!label.is_empty() && label.len() <= MAX_LABEL_LEN,"Invalid classification {}",label);
+        }
+        if let Some(license) = &bibliography.license {
+            assert!(license.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '+'),
+                "License should be an SPDX identifier!");
+        }
+        assert!(bibliography.funding.len() <= MAX_FUNDING,"At most {} funding acknowledgements!",MAX_FUNDING);
+        for funding in bibliography.funding.iter() {
+            assert!(funding.len() <= MAX_SUMMARY_LEN,"Funding acknowledgement is too long!");
+        }
+        assert!(bibliography.orcids.is_empty() || bibliography.orcids.len() == authors,
+            "Give one ORCID iD (or null) per author!");
+        for orcid in bibliography.orcids.iter().flatten() {
+            assert!(valid_orcid(orcid),"Invalid ORCID iD {}",orcid);
+        }
+        assert!(bibliography.author_accounts.len() <= authors,"More author accounts than authors!");
+        for (i,account_id) in bibliography.author_accounts.iter().enumerate() {
+            assert!(!bibliography.author_accounts[..i].contains(account_id),"Duplicate author account {}",account_id);
+        }
+    }
+
+    // Bibliographic record carried into the `extra` of the minted token.
+    pub(crate) fn bibliographic_extra(paper: &PaperMetadata) -> serde_json::Value {
+        let bibliography = &paper.bibliography;
+        let authors: Vec<serde_json::Value> = paper.author.iter().enumerate().map(|(i,name)| {
+            serde_json::json!({
+                "name": name,
+                "orcid": bibliography.orcids.get(i).cloned().flatten(),
+            })
+        }).collect();
+
+        serde_json::json!({
+            "abstract": paper.versions.last().unwrap().r#abstract,
+            "authors": authors,
+            "keywords": bibliography.keywords,
+            "field": bibliography.field,
+            "language": bibliography.language,
+            "license": bibliography.license,
+            "funding": bibliography.funding,
+        })
+    }
+
+    // Token metadata of a paper being published, with the fields set in `overrides` replacing
+    // the derived ones. The media always is the version the reviewers accepted.
+    pub(crate) fn paper_token_metadata(&self, paper: &PaperMetadata, overrides: Option<TokenMetadata>) -> TokenMetadata {
+        let version = paper.versions.last().unwrap();
+        let overrides = overrides.unwrap_or(TokenMetadata {
+            title: None, description: None, media: None, media_hash: None, copies: None, issued_at: None,
+            expires_at: None, starts_at: None, updated_at: None, extra: None, reference: None, reference_hash: None,
+        });
+
+        let mut extra = serde_json::Map::new();
+        if let serde_json::Value::Object(fields) = Self::bibliographic_extra(paper) {
+            extra.extend(fields);
+        }
+        extra.insert("review".to_string(), self.review_summary(paper));
+        extra.extend(extra_fields(&overrides.extra));
+
+        TokenMetadata {
+            title:          overrides.title.or_else(|| Some(paper.title.clone())),
+            description:    overrides.description.or_else(|| Some(version.r#abstract.clone())),
+            media:          Some(version.media.clone()),
+            media_hash:     Some(version.media_hash.clone()),
+            copies:         overrides.copies.or(Some(1)),
+            issued_at:      overrides.issued_at.or(Some(env::block_timestamp() / 1_000_000)),
+            expires_at:     overrides.expires_at,
+            starts_at:      overrides.starts_at,
+            updated_at:     overrides.updated_at,
+            extra:          Some(serde_json::Value::Object(extra).to_string()),
+            reference:      overrides.reference,
+            reference_hash: overrides.reference_hash,
+        }
+    }
+
+    fn review_summary(&self, paper: &PaperMetadata) -> serde_json::Value {
+        let scores: Vec<serde_json::Value> = paper.scores.iter().map(|criterion| {
+            serde_json::json!({"criterion": criterion.criterion, "average": criterion.average})
+        }).collect();
+        serde_json::json!({
+            "policy": paper.policy,
+            "round": paper.round,
+            "vote_yes": paper.vote_yes,
+            "vote_rev": paper.vote_rev,
+            "vote_no": paper.vote_no,
+            "scores": scores,
+            "editor_decision": paper.meta_review.as_ref().map(|meta_review| meta_review.decision),
+        })
+    }
+}
+
+// ORCID iDs look like 0000-0002-1825-0097, the last character being an ISO 7064 11,2 check digit.
+fn valid_orcid(orcid: &str) -> bool {
+    let digits: Vec<char> = orcid.chars().filter(|c| *c != '-').collect();
+    if orcid.len() != 19 || digits.len() != 16 || orcid.split('-').any(|group| group.len() != 4) {
+        return false;
+    }
+    let mut total = 0;
+    for c in digits[..15].iter() {
+        match c.to_digit(10) {
+            Some(d) => total = (total + d) * 2,
+            None => return false,
+        }
+    }
+    let check = (12 - total % 11) % 11;
+    let expected = if check == 10 { 'X' } else { std::char::from_digit(check, 10).unwrap() };
+    digits[15] == expected
+}
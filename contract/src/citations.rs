@@ -0,0 +1,68 @@
+use crate::*;
+use crate::index::page;
+
+const MAX_REFERENCES: usize = 200;
+const MAX_DOI_LEN: usize = 256;
+
+#[near_bindgen]
+impl Contract {
+    // Works cited by a paper, in the order given at submission.
+    pub fn references_of(&self, token_id: &TokenId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Citation> {
+        let paper = self.papersmetadata.get(token_id).unwrap();
+        paper.bibliography.references.into_iter()
+            .skip(u128::from(from_index.unwrap_or(U128(0))) as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .collect()
+    }
+
+    // Published papers citing a paper, in order of publication.
+    pub fn cited_by(&self, token_id: &TokenId, from_index: Option<U128>, limit: Option<u64>) -> Vec<TokenId> {
+        match self.cited_by.get(token_id) {
+            Some(citing) => page(&citing,from_index,limit),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn check_references(&self, token_id: &TokenId, references: &[Citation]) {
+        assert!(references.len() <= MAX_REFERENCES,"At most {} references!",MAX_REFERENCES);
+        for (i,citation) in references.iter().enumerate() {
+            assert!(!references[..i].contains(citation),"Duplicate reference {:?}",citation);
+            match citation {
+                Citation::Paper(cited) => {
+                    assert!(cited != token_id,"A paper cannot cite itself!");
+                    assert!(self.papersmetadata.get(cited).is_some(),"Unknown paper {} in references",cited);
+                }
+                Citation::Doi(doi) => {
+                    assert!(doi.starts_with("10.") && doi.contains('/') && doi.len() <= MAX_DOI_LEN,"Invalid DOI {}",doi);
+                }
+            }
+        }
+    }
+
+    // Citations only count once the citing paper is published.
+    pub(crate) fn record_citations(&mut self, token_id: &TokenId, references: &[Citation]) {
+        let mut owners = Vec::new();
+        for citation in references {
+            if let Citation::Paper(cited) = citation {
+                let mut citing = self.cited_by.get(cited).unwrap_or_else(|| {
+                    Vector::new(StorageKey::CitedByInner {
+                        token_id_hash: hash_token_id(cited),
+                    }.try_to_vec().unwrap())
+                });
+                citing.push(token_id);
+                self.cited_by.insert(cited,&citing);
+
+                let mut paper = self.papersmetadata.get(cited).unwrap();
+                paper.citation_count += 1;
+                self.papersmetadata.insert(cited,&paper);
+
+                if let Some(token) = self.paperid.get(cited) {
+                    owners.push(token.owner_id);
+                }
+            }
+        }
+        self.reward_citations(&owners);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::*;
+
+const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+#[near_bindgen]
+impl Contract {
+    pub fn review_credits(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<ReviewCredit> {
+        match self.credits_by_reviewer.get(&account_id) {
+            Some(credits) => crate::index::page(&credits,from_index,limit).into_iter()
+                .map(|token_id| self.credits.get(&token_id).unwrap())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // Credits for anonymous reviews waiting to be claimed by the account.
+    pub fn unclaimed_credits(&self, account_id: AccountId) -> u32 {
+        self.unclaimed_credits.get(&account_id).unwrap_or(0)
+    }
+
+    // Mints the credits for the anonymous reviews of the caller. Minting them in a call of the
+    // reviewer's choosing, with the day as only date, keeps the credits from being matched with
+    // the decision on the paper. The caller pays the storage.
+    #[payable]
+    pub fn claim_review_credits(&mut self) {
+        let initial_storage_usage = env::storage_usage();
+        let account_id = env::predecessor_account_id();
+        let unclaimed = self.unclaimed_credits.remove(&account_id).unwrap_or(0);
+        assert!(unclaimed > 0,"No review credits to claim!");
+        let issued_at = env::block_timestamp() / DAY * DAY;
+        for _ in 0..unclaimed {
+            let credit = ReviewCredit {
+                token_id:   format!("{}{}",CREDIT_PREFIX,self.credits.len()),
+                paper_id:   None,
+                round:      None,
+                issued_at:  U64(issued_at),
+            };
+            self.mint_credit(&account_id,credit,"Anonymous review".to_string(),serde_json::json!({"soulbound": true}));
+        }
+        Contract::refund_deposit(env::storage_usage() - initial_storage_usage);
+    }
+}
+
+impl Contract {
+    // Mints a credit to every reviewer that voted in the final round of a paper, after they
+    // were payed. The credit names the paper, never the vote. Under an anonymous policy nothing
+    // is minted here: the reviewers claim their credits later with `claim_review_credits`.
+    // Its storage is payed from the paper's escrow.
+    pub(crate) fn issue_review_credits(&mut self, token_id: &TokenId, paper: &mut PaperMetadata) {
+        let initial_storage_usage = env::storage_usage();
+        let anonymous = self.policies.get(&paper.policy).unwrap().anonymous;
+        for (account_id,revdata) in paper.reviewers.iter() {
+            if revdata.accept != Approval::Approved || revdata.vote == Vote::NotVoted {
+                continue;
+            }
+            if anonymous {
+                let unclaimed = self.unclaimed_credits.get(account_id).unwrap_or(0);
+                self.unclaimed_credits.insert(account_id,&(unclaimed + 1));
+                continue;
+            }
+            let credit = ReviewCredit {
+                token_id:   format!("{}{}",CREDIT_PREFIX,self.credits.len()),
+                paper_id:   Some(token_id.clone()),
+                round:      Some(paper.round),
+                issued_at:  U64(env::block_timestamp()),
+            };
+            let description = format!("Review of paper {} ({}), round {}",token_id,paper.title,paper.round);
+            let extra = serde_json::json!({"paper": token_id, "round": paper.round, "soulbound": true});
+            self.mint_credit(account_id,credit,description,extra);
+        }
+        self.pay_storage(paper,env::storage_usage() - initial_storage_usage);
+    }
+
+    fn mint_credit(&mut self, account_id: &AccountId, credit: ReviewCredit, description: String, extra: serde_json::Value) {
+        let metadata = TokenMetadata {
+            title:          Some("Peer review credit".to_string()),
+            description:    Some(description),
+            media:          None,
+            media_hash:     None,
+            copies:         Some(1),
+            issued_at:      Some(credit.issued_at.0 / 1_000_000),
+            expires_at:     None,
+            starts_at:      None,
+            updated_at:     None,
+            extra:          Some(extra.to_string()),
+            reference:      None,
+            reference_hash: None,
+        };
+        self.internal_mint(credit.token_id.clone(),metadata,account_id.clone());
+
+        let mut credits = self.credits_by_reviewer.get(account_id).unwrap_or_else(|| {
+            Vector::new(StorageKey::CreditsByReviewerInner {
+                account_id_hash: hash_account_id(account_id),
+            }.try_to_vec().unwrap())
+        });
+        credits.push(&credit.token_id);
+        self.credits_by_reviewer.insert(account_id,&credits);
+        self.credits.insert(&credit.token_id,&credit);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::*;
+use crate::index::{page, review_state};
+use std::ops::Bound;
+
+#[near_bindgen]
+impl Contract {
+    // Papers an account was invited to review, optionally only those in one state. Papers under
+    // anonymous policies are left out, as listing them would tie the account to the paper. Like
+    // `list_papers`, `from_index` is the submission number of the first paper listed.
+    pub fn reviews_for(&self, account_id: AccountId, status: Option<ReviewState>, from_index: Option<U128>, limit: Option<u64>) -> Vec<ReviewAssignment> {
+        self.review_page(ReviewIndex { account_id, anonymous: false, state: status },from_index,limit)
+    }
+
+    // The reviews left out of `reviews_for`, for the reviewer holding the view key of the
+    // account. The reviewer is named by their handle on each paper.
+    pub fn anonymous_reviews_for(&self, account_id: AccountId, view_key: String, status: Option<ReviewState>,
+        from_index: Option<U128>, limit: Option<u64>) -> Vec<ReviewAssignment> {
+        self.check_view_key(&account_id,&view_key);
+        self.review_page(ReviewIndex { account_id, anonymous: true, state: status },from_index,limit)
+    }
+
+    pub fn papers_by_submitter(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<SubmissionView> {
+        match self.papers_by_submitter.get(&account_id) {
+            Some(papers) => page(papers.as_vector(),from_index,limit).into_iter().map(|token_id| self.submission_view(token_id)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // Papers listing the account in the `author_accounts` of their bibliography.
+    pub fn papers_by_author_account(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<SubmissionView> {
+        match self.papers_by_author.get(&account_id) {
+            Some(papers) => page(papers.as_vector(),from_index,limit).into_iter().map(|token_id| self.submission_view(token_id)).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Contract {
+    fn review_page(&self, key: ReviewIndex, from_index: Option<U128>, limit: Option<u64>) -> Vec<ReviewAssignment> {
+        let papers = match self.papers_by_reviewer.get(&key) {
+            Some(papers) => papers,
+            None => return Vec::new(),
+        };
+        let start = u128::from(from_index.unwrap_or(U128(0))) as u64;
+        papers.range((Bound::Included(start), Bound::Unbounded))
+            .take(limit.unwrap_or(50) as usize)
+            .map(|(_,token_id)| self.review_assignment(&key.account_id,token_id))
+            .collect()
+    }
+
+    fn submission_view(&self, token_id: TokenId) -> SubmissionView {
+        let a = self.papersmetadata.get(&token_id).unwrap();
+        let hidden = self.votes_hidden(&a);
+        let anonymous = self.policies.get(&a.policy).unwrap().anonymous;
+
+        let reviewers = a.reviewers.into_iter().map(|(account_id,revdata)| ReviewerProgress {
+            reviewer:   if anonymous { revdata.handle } else { account_id.to_string() },
+            accept:     revdata.accept,
+            voted:      revdata.vote != Vote::NotVoted || revdata.commitment.is_some(),
+            vote:       Some(revdata.vote).filter(|vote| !hidden && *vote != Vote::NotVoted),
+            payed:      revdata.payedrev == Pay::Payed,
+        }).collect();
+
+        SubmissionView {
+            token_id,
+            title:      a.title,
+            status:     a.status,
+            round:      a.round,
+            deadline:   a.deadline,
+            escrow:     a.escrow,
+            reviewers,
+        }
+    }
+
+    fn review_assignment(&self, account_id: &AccountId, token_id: TokenId) -> ReviewAssignment {
+        let a = self.papersmetadata.get(&token_id).unwrap();
+        let revdata = a.reviewers.get(account_id);
+        ReviewAssignment {
+            token_id,
+            number:         U64(a.number),
+            title:          a.title.clone(),
+            paper_status:   a.status,
+            round:          a.round,
+            deadline:       a.deadline,
+            state:          review_state(&a,revdata),
+            handle:         revdata.map(|revdata| revdata.handle.clone()),
+            stake:          revdata.map_or(U128(0),|revdata| revdata.stake),
+            committed:      matches!(revdata, Some(revdata) if revdata.commitment.is_some() && revdata.vote == Vote::NotVoted),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::*;
+
+const MAX_MESSAGE_LEN: usize = 4096;
+
+#[near_bindgen]
+impl Contract {
+    // Appends a message to the discussion of a paper. Only the submitter, the reviewers that
+    // accepted the invitation and the editors can post. The sender pays for the storage.
+    #[payable]
+    pub fn post_message(&mut self, token_id: &TokenId, body: MessageBody) {
+        let initial_storage_usage = env::storage_usage();
+        let account_id = env::predecessor_account_id();
+        let a = self.papersmetadata.get(token_id).unwrap();
+
+        let (role,handle) = if account_id == a.submitter {
+            (Role::Author,None)
+        } else if let Some(revdata) = a.reviewers.get(&account_id).filter(|revdata| revdata.accept == Approval::Approved) {
+            (Role::Reviewer,Some(revdata.handle.clone()))
+        } else if self.is_editor(&account_id) {
+            (Role::Editor,None)
+        } else {
+            panic!("Not allowed to post on this paper!")
+        };
+
+        match &body {
+            MessageBody::Text(text) => assert!(text.len() <= MAX_MESSAGE_LEN,"Message longer than {} bytes!",MAX_MESSAGE_LEN),
+            MessageBody::Reference{hash,..} => assert_eq!(hash.0.len(),32,"Message hash should be a sha256 hash!"),
+        }
+
+        let mut thread = self.discussions.get(token_id).unwrap_or_else(|| {
+            Vector::new(StorageKey::DiscussionInner {
+                token_id_hash: hash_token_id(token_id),
+            }.try_to_vec().unwrap())
+        });
+        thread.push(&DiscussionMessage {
+            sender:     account_id,
+            handle,
+            role,
+            round:      a.round,
+            posted_at:  U64(env::block_timestamp()),
+            body,
+        });
+        self.discussions.insert(token_id,&thread);
+
+        Contract::refund_deposit(env::storage_usage() - initial_storage_usage);
+    }
+
+    pub fn discussion(&self, token_id: &TokenId, from_index: Option<U128>, limit: Option<u64>) -> Vec<DiscussionMessageView> {
+        let thread = match self.discussions.get(token_id) {
+            Some(thread) => thread,
+            None => return Vec::new(),
+        };
+        let paper = self.papersmetadata.get(token_id).unwrap();
+        let anonymous = self.policies.get(&paper.policy).unwrap().anonymous;
+
+        let start = u128::from(from_index.unwrap_or(U128(0))) as u64;
+        let end = std::cmp::min(thread.len(), start.saturating_add(limit.unwrap_or(50)));
+        (start..end).map(|index| {
+            let message = thread.get(index).unwrap();
+            DiscussionMessageView {
+                sender: match message.handle {
+                    Some(handle) if anonymous => handle,
+                    _ => message.sender.to_string(),
+                },
+                role:       message.role,
+                round:      message.round,
+                posted_at:  message.posted_at,
+                body:       message.body,
+            }
+        }).collect()
+    }
+}
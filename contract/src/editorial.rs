@@ -0,0 +1,274 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    pub fn add_editor(&mut self, account_id: AccountId) {
+        assert_eq!(&env::predecessor_account_id(),&env::current_account_id(),"Not Owner!");
+        self.editors.insert(&account_id);
+    }
+
+    pub fn remove_editor(&mut self, account_id: AccountId) {
+        assert_eq!(&env::predecessor_account_id(),&env::current_account_id(),"Not Owner!");
+        self.editors.remove(&account_id);
+    }
+
+    pub fn view_editors(&self) -> Vec<AccountId> {
+        self.editors.to_vec()
+    }
+
+    // Final call of a handling editor on a paper under review. It records a meta-review and
+    // accepts or rejects the paper regardless of the raw votes; reviewers that voted are payed.
+    pub fn editor_decision(&mut self, token_id: &TokenId, decision: Decision, justification_hash: Base64VecU8) {
+        let editor = env::predecessor_account_id();
+        assert!(self.is_editor(&editor),"Not an editor!");
+        assert_eq!(justification_hash.0.len(),32,"Justification hash should be a sha256 hash!");
+
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert!(a.status == Status::Unpublished,"Review closed!");
+
+        let overrides_votes = match decision {
+            Decision::Accept => !self.votes_accept(&a),
+            Decision::Reject => a.vote_no == 0,
+        };
+
+        let decision_log: EventLog = EventLog {
+            standard: JOURNAL_STANDARD_NAME.to_string(),
+            version: JOURNAL_EVENT_VERSION.to_string(),
+            event: EventLogVariant::EditorDecision(vec![EditorDecisionLog {
+                token_id: token_id.to_string(),
+                editor_id: editor.to_string(),
+                decision,
+                overrides_votes,
+            }]),
+        };
+        env::log_str(&decision_log.to_string());
+
+        match decision {
+            Decision::Accept => {
+                self.record_outcome(&a,true);
+                self.pay_reviewers(&mut a);
+                self.return_stakes(&mut a);
+                self.issue_review_credits(token_id,&mut a);
+                self.settle_appeal(token_id,&mut a,true);
+                self.set_status(token_id,&mut a,Status::Accepted);
+            }
+            Decision::Reject => self.finalize_rejected(token_id,&mut a),
+        }
+        a.meta_review = Some(MetaReview {
+            editor,
+            decision,
+            justification_hash,
+            round:          a.round,
+            decided_at:     U64(env::block_timestamp()),
+            overrides_votes,
+        });
+        self.papersmetadata.insert(token_id,&a);
+    }
+
+    // Token metadata fields an editor wants on the NFT of a paper instead of the derived ones,
+    // applied when it is published. Overrides passed to `publish` take precedence.
+    pub fn set_token_overrides(&mut self, token_id: &TokenId, overrides: TokenMetadata) {
+        assert!(self.is_editor(&env::predecessor_account_id()),"Not an editor!");
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert!(a.status == Status::Unpublished || a.status == Status::Accepted,"Paper cannot be published!");
+        a.token_overrides = Some(overrides);
+        self.papersmetadata.insert(token_id,&a);
+    }
+
+    // The submitter of a rejected paper can appeal once, attaching the appeal fee of the policy.
+    #[payable]
+    pub fn appeal(&mut self, token_id: &TokenId, reason_reference: String) {
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert_eq!(env::predecessor_account_id(),a.submitter,"Only the submitter can appeal!");
+        assert!(a.status == Status::Rejected,"Only rejected papers can be appealed!");
+        assert!(a.appeal.is_none(),"Already appealed!");
+        let fee = self.policies.get(&a.policy).unwrap().appeal_fee;
+        assert_eq!(env::attached_deposit(),fee.0,"Should deposit {} yoctoNEAR.",fee.0);
+
+        self.hold_escrow(fee.0);
+        a.appeal = Some(Appeal {
+            reason_reference,
+            fee,
+            filed_at:   U64(env::block_timestamp()),
+            round:      None,
+            outcome:    AppealOutcome::Pending,
+        });
+        self.set_status(token_id,&mut a,Status::Appealed);
+        self.papersmetadata.insert(token_id,&a);
+    }
+
+    // Withdraws an appeal no panel was assigned to yet, e.g. because the editors did not act
+    // on it. The fee is refunded and the rejection stands.
+    pub fn withdraw_appeal(&mut self, token_id: &TokenId) {
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert_eq!(env::predecessor_account_id(),a.submitter,"Only the submitter can withdraw an appeal!");
+        assert!(a.status == Status::Appealed,"Paper is not waiting for an appeal panel!");
+
+        let appeal = a.appeal.as_mut().unwrap();
+        appeal.outcome = AppealOutcome::Withdrawn;
+        self.record_refund(appeal.fee.0);
+        Promise::new(a.submitter.clone()).transfer(appeal.fee.0);
+        env::log_str(&format!("Appeal on paper {} {:?}",token_id,appeal.outcome));
+        self.set_status(token_id,&mut a,Status::Rejected);
+        self.papersmetadata.insert(token_id,&a);
+    }
+
+    // Re-opens an appealed paper under a fresh panel in a new review round. The appeal fee
+    // becomes the escrow the panel is payed from.
+    pub fn assign_appeal_panel(&mut self, token_id: &TokenId, accrev: Vec<AccountId>) {
+        assert!(self.is_editor(&env::predecessor_account_id()),"Not an editor!");
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert!(a.status == Status::Appealed,"Paper is not waiting for an appeal panel!");
+        assert!(accrev.len()==REVIEWERS,"It should be 3 reviewers!");
+
+        let mut rev = HashMap::new();
+        for account_id in accrev {
+            assert!(account_id != a.submitter,"Submitter Cannot be Reviewer");
+            assert!(!a.reviewers.contains_key(&account_id),"{} already reviewed this paper",account_id);
+            a.invited += 1;
+            rev.insert(account_id,Reviewdata::new(Approval::AwaitApprov,format!("Reviewer {}",a.invited)));
+        }
+
+        let policy = self.policies.get(&a.policy).unwrap();
+        for account_id in rev.keys() {
+            self.check_reputation(&policy,account_id);
+        }
+        a.reviewers = rev;
+        a.vote_yes = 0;
+        a.vote_rev = 0;
+        a.vote_no = 0;
+        a.scores = policy.rubric.into_iter().map(CriterionScore::new).collect();
+        a.round += 1;
+        a.deadline = U64(env::block_timestamp() + policy.review_period.0);
+        let appeal = a.appeal.as_mut().unwrap();
+        appeal.round = Some(a.round);
+        a.escrow = appeal.fee;
+        self.set_status(token_id,&mut a,Status::Unpublished);
+        self.papersmetadata.insert(token_id,&a);
+    }
+
+    // Marks a published paper as retracted. The NFT stays with its owner.
+    pub fn retract(&mut self, token_id: &TokenId, reason_hash: Base64VecU8) {
+        let editor = env::predecessor_account_id();
+        assert!(self.is_editor(&editor),"Not an editor!");
+        assert_eq!(reason_hash.0.len(),32,"Reason hash should be a sha256 hash!");
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert!(a.status == Status::Published,"Only published papers can be retracted!");
+
+        let retraction_log: EventLog = EventLog {
+            standard: JOURNAL_STANDARD_NAME.to_string(),
+            version: JOURNAL_EVENT_VERSION.to_string(),
+            event: EventLogVariant::PaperRetraction(vec![PaperRetractionLog {
+                token_id: token_id.to_string(),
+                editor_id: editor.to_string(),
+                reason_hash: reason_hash.clone(),
+            }]),
+        };
+        env::log_str(&retraction_log.to_string());
+
+        a.retraction = Some(Retraction {
+            editor,
+            reason_hash,
+            retracted_at:   U64(env::block_timestamp()),
+        });
+        self.set_status(token_id,&mut a,Status::Retracted);
+        self.update_token_notices(token_id,&a);
+        self.papersmetadata.insert(token_id,&a);
+    }
+
+    pub fn issue_erratum(&mut self, token_id: &TokenId, erratum_reference: String) {
+        let editor = env::predecessor_account_id();
+        assert!(self.is_editor(&editor),"Not an editor!");
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert!(a.status == Status::Published,"Only published papers can be corrected!");
+
+        let erratum_log: EventLog = EventLog {
+            standard: JOURNAL_STANDARD_NAME.to_string(),
+            version: JOURNAL_EVENT_VERSION.to_string(),
+            event: EventLogVariant::PaperErratum(vec![PaperErratumLog {
+                token_id: token_id.to_string(),
+                editor_id: editor.to_string(),
+                reference: erratum_reference.clone(),
+            }]),
+        };
+        env::log_str(&erratum_log.to_string());
+
+        a.errata.push(Erratum {
+            editor,
+            reference:  erratum_reference,
+            issued_at:  U64(env::block_timestamp()),
+        });
+        self.update_token_notices(token_id,&a);
+        self.papersmetadata.insert(token_id,&a);
+    }
+
+    // Registers the sha256 hash of a secret that unlocks the views tying an account to its
+    // anonymous reviews. Views cannot tell who calls them, and the return value of a call ends up
+    // in a public receipt, so the secret is only ever sent to views, which run off chain.
+    // Anonymity stays at the view level: the reviewers of a paper are stored under their
+    // `AccountId` and anyone can read them from the raw contract state.
+    #[payable]
+    pub fn set_view_key(&mut self, key_hash: Base64VecU8) {
+        assert_eq!(key_hash.0.len(),32,"View key hash should be a sha256 hash!");
+        let initial_storage_usage = env::storage_usage();
+        self.view_keys.insert(&env::predecessor_account_id(),&key_hash.0);
+        Contract::refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+    }
+
+    // Resolves an anonymous reviewer handle to the account behind it, for an editor holding
+    // their view key.
+    pub fn resolve_reviewer(&self, token_id: &TokenId, handle: String, editor_id: AccountId, view_key: String) -> AccountId {
+        assert!(self.is_editor(&editor_id),"Not an editor!");
+        self.check_view_key(&editor_id,&view_key);
+        let a = self.papersmetadata.get(token_id).unwrap();
+        a.reviewers.into_iter()
+            .find(|(_,revdata)| revdata.handle == handle)
+            .map(|(account_id,_)| account_id)
+            .expect("Unknown reviewer handle!")
+    }
+
+    // Lets a reviewer find out which handle is theirs on an anonymous paper.
+    pub fn my_reviewer_handle(&self, token_id: &TokenId, account_id: AccountId, view_key: String) -> String {
+        self.check_view_key(&account_id,&view_key);
+        let a = self.papersmetadata.get(token_id).unwrap();
+        a.reviewers.get(&account_id).expect("Not a reviewer!").handle.clone()
+    }
+}
+
+impl Contract {
+    pub(crate) fn check_view_key(&self, account_id: &AccountId, view_key: &str) {
+        let key_hash = self.view_keys.get(account_id).expect("No view key registered!");
+        assert!(env::sha256(view_key.as_bytes()) == key_hash,"Wrong view key!");
+    }
+
+    // The contract account itself is always an editor.
+    pub(crate) fn is_editor(&self, account_id: &AccountId) -> bool {
+        account_id == &env::current_account_id() || self.editors.contains(account_id)
+    }
+
+    // Closes a pending appeal. The fee payed the panel, so it stays spent either way.
+    pub(crate) fn settle_appeal(&mut self, token_id: &TokenId, paper: &mut PaperMetadata, succeeded: bool) {
+        let appeal = match paper.appeal.as_mut() {
+            Some(appeal) if appeal.outcome == AppealOutcome::Pending => appeal,
+            _ => return,
+        };
+        appeal.outcome = if succeeded { AppealOutcome::Overturned } else { AppealOutcome::Upheld };
+        env::log_str(&format!("Appeal on paper {} {:?}",token_id,appeal.outcome));
+    }
+
+    // Mirrors retraction and errata into the token metadata so that marketplaces reading
+    // `nft_token` can flag the paper. Keys already in `extra` are kept.
+    fn update_token_notices(&mut self, token_id: &TokenId, paper: &PaperMetadata) {
+        let mut metadata = self.tokenmetadata.get(token_id).unwrap();
+        let mut extra = extra_fields(&metadata.extra);
+        extra.insert("retracted".to_string(), paper.retraction.is_some().into());
+        if let Some(retraction) = &paper.retraction {
+            extra.insert("retraction_reason_hash".to_string(), serde_json::to_value(&retraction.reason_hash).unwrap());
+        }
+        extra.insert("errata".to_string(), paper.errata.iter().map(|erratum| erratum.reference.clone()).collect());
+
+        metadata.extra = Some(serde_json::Value::Object(extra).to_string());
+        metadata.updated_at = Some(env::block_timestamp() / 1_000_000);
+        self.tokenmetadata.insert(token_id,&metadata);
+    }
+}
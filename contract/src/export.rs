@@ -0,0 +1,130 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    pub fn paper_bibtex(&self, token_id: &TokenId) -> String {
+        let a = self.papersmetadata.get(token_id).unwrap();
+        let published_at = a.published_at.expect("Paper not published yet!");
+        let (year,month,_) = civil_date(published_at.0);
+        let journal = self.metadata.get().unwrap().name;
+
+        let mut fields = vec![
+            ("title", bibtex_escape(&a.title)),
+            ("author", a.author.iter().map(|name| bibtex_escape(name)).collect::<Vec<_>>().join(" and ")),
+            ("journal", bibtex_escape(&journal)),
+            ("year", year.to_string()),
+            ("month", month.to_string()),
+        ];
+        if !a.bibliography.keywords.is_empty() {
+            fields.push(("keywords", bibtex_escape(&a.bibliography.keywords.join(", "))));
+        }
+        if let Some(url) = self.token_url(token_id) {
+            fields.push(("url", bibtex_url(&url)));
+        }
+        fields.push(("note", bibtex_escape(&self.token_note(token_id, &a))));
+
+        let body: Vec<String> = fields.iter().map(|(name,value)| format!("  {} = {{{}}}",name,value)).collect();
+        format!("@article{{{},\n{}\n}}\n", citation_key(token_id, &a, year), body.join(",\n"))
+    }
+
+    pub fn paper_csl_json(&self, token_id: &TokenId) -> CslItem {
+        let a = self.papersmetadata.get(token_id).unwrap();
+        let published_at = a.published_at.expect("Paper not published yet!");
+        let (year,month,day) = civil_date(published_at.0);
+
+        CslItem {
+            id:                 citation_key(token_id, &a, year),
+            item_type:          "article-journal".to_string(),
+            title:              a.title.clone(),
+            author:             a.author.iter().map(|name| csl_name(name)).collect(),
+            container_title:    self.metadata.get().unwrap().name,
+            issued:             CslDate { date_parts: vec![vec![year,month,day]] },
+            r#abstract:         a.versions.last().unwrap().r#abstract.clone(),
+            keyword:            Some(a.bibliography.keywords.join(", ")).filter(|keywords| !keywords.is_empty()),
+            language:           a.bibliography.language.clone(),
+            url:                self.token_url(token_id),
+            note:               self.token_note(token_id, &a),
+        }
+    }
+}
+
+impl Contract {
+    // The token under the contract base URI, or else the accepted manuscript.
+    fn token_url(&self, token_id: &TokenId) -> Option<String> {
+        match self.metadata.get().unwrap().base_uri {
+            Some(base_uri) => Some(format!("{}/{}",base_uri.trim_end_matches('/'),token_id)),
+            None => self.tokenmetadata.get(token_id).and_then(|metadata| metadata.media),
+        }
+    }
+
+    fn token_note(&self, token_id: &TokenId, paper: &PaperMetadata) -> String {
+        let note = format!("NEAR NFT {} token {}",env::current_account_id(),token_id);
+        if paper.retraction.is_some() { format!("{}. Retracted",note) } else { note }
+    }
+}
+
+// "nakamoto2008_0": family name of the first author, year and token id.
+fn citation_key(token_id: &TokenId, paper: &PaperMetadata, year: u32) -> String {
+    let first = paper.author.first().map(|name| name.split(',').next().unwrap().to_string()).unwrap_or_default();
+    let name: String = first.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    let token: String = token_id.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect();
+    format!("{}{}_{}",name.to_lowercase(),year,token)
+}
+
+fn csl_name(name: &str) -> CslName {
+    match name.split_once(',') {
+        Some((family,given)) => CslName {
+            family:     Some(family.trim().to_string()),
+            given:      Some(given.trim().to_string()),
+            literal:    None,
+        },
+        None => CslName { family: None, given: None, literal: Some(name.to_string()) },
+    }
+}
+
+pub(crate) fn bibtex_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// URLs are percent-encoded instead of escaped so that they stay valid links. Bytes outside the
+// URI characters, and the ones TeX treats specially, are encoded; existing escapes are kept.
+pub(crate) fn bibtex_url(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut encoded = String::with_capacity(value.len());
+    for (i,byte) in bytes.iter().enumerate() {
+        let percent_escape = *byte == b'%' && matches!(bytes.get(i+1..i+3), Some(hex) if hex.iter().all(u8::is_ascii_hexdigit));
+        if byte.is_ascii_alphanumeric() || b"-._:/?#[]@!$&'()*+,;=".contains(byte) || percent_escape {
+            encoded.push(char::from(*byte));
+        } else {
+            encoded.push_str(&format!("%{:02X}",byte));
+        }
+    }
+    encoded
+}
+
+// Year, month and day of a block timestamp in nanoseconds (proleptic Gregorian calendar, UTC).
+fn civil_date(timestamp: u64) -> (u32, u32, u32) {
+    let days = (timestamp / 1_000_000_000 / 86_400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = (year_of_era + era * 400) as u32 + if month <= 2 { 1 } else { 0 };
+    (year,month,day)
+}
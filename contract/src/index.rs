@@ -0,0 +1,145 @@
+use crate::*;
+use std::ops::Bound;
+
+const REVIEW_STATES: [ReviewState; 4] = [
+    ReviewState::Invited, ReviewState::AwaitingVote, ReviewState::Unpaid, ReviewState::Completed,
+];
+
+#[near_bindgen]
+impl Contract {
+    // Papers in submission order, or only those with the given status. `from_index` is the
+    // submission number of the first paper listed, so that pages stay put while papers change
+    // status: the next page starts after the `number` of the last paper.
+    pub fn list_papers(&self, from_index: Option<U128>, limit: Option<u64>, status_filter: Option<Status>) -> Vec<PaperSummary> {
+        let token_ids = match status_filter {
+            Some(status) => match self.papers_by_status.get(&status) {
+                Some(papers) => {
+                    let start = u128::from(from_index.unwrap_or(U128(0))) as u64;
+                    papers.range((Bound::Included(start), Bound::Unbounded))
+                        .take(limit.unwrap_or(50) as usize)
+                        .map(|(_,token_id)| token_id)
+                        .collect()
+                }
+                None => Vec::new(),
+            },
+            None => page(self.papersmetadata.keys_as_vector(),from_index,limit),
+        };
+        token_ids.into_iter().map(|token_id| {
+            let a = self.papersmetadata.get(&token_id).unwrap();
+            PaperSummary {
+                token_id,
+                number:         U64(a.number),
+                title:          a.title,
+                author:         a.author,
+                status:         a.status,
+                submitter:      a.submitter,
+                round:          a.round,
+                policy:         a.policy,
+                published_at:   a.published_at,
+                citation_count: a.citation_count,
+            }
+        }).collect()
+    }
+
+    // Like `view_paper_meta`, without panicking on an unknown id.
+    pub fn get_paper(&self, token_id: &TokenId) -> Option<PaperView> {
+        self.papersmetadata.get(token_id).map(|paper| self.paper_view(paper))
+    }
+}
+
+impl Contract {
+    pub(crate) fn index_paper(&mut self, token_id: &TokenId, paper: &PaperMetadata) {
+        self.status_insert(paper.status,paper.number,token_id);
+        index_insert(&mut self.papers_by_submitter,&paper.submitter,
+            StorageKey::PapersBySubmitterInner { account_id_hash: hash_account_id(&paper.submitter) },token_id);
+        for account_id in paper.bibliography.author_accounts.iter() {
+            index_insert(&mut self.papers_by_author,account_id,
+                StorageKey::PapersByAuthorInner { account_id_hash: hash_account_id(account_id) },token_id);
+        }
+        self.index_reviews(token_id,paper);
+    }
+
+    // Files every reviewer of a paper under the current state of their review, so that
+    // dashboards page through the index without loading the papers. Runs after anything that
+    // can move a review to another state: status changes, invitations, votes and payouts.
+    pub(crate) fn index_reviews(&mut self, token_id: &TokenId, paper: &PaperMetadata) {
+        let anonymous = self.policies.get(&paper.policy).unwrap().anonymous;
+        for (account_id,revdata) in paper.reviewers.iter() {
+            let state = review_state(paper,Some(revdata));
+            for other in REVIEW_STATES.iter().filter(|other| **other != state) {
+                self.review_index_remove(ReviewIndex { account_id: account_id.clone(), anonymous, state: Some(*other) },paper.number);
+            }
+            self.review_index_insert(ReviewIndex { account_id: account_id.clone(), anonymous, state: None },paper.number,token_id);
+            self.review_index_insert(ReviewIndex { account_id: account_id.clone(), anonymous, state: Some(state) },paper.number,token_id);
+        }
+    }
+
+    // Drops a reviewer that declined the invitation from the index.
+    pub(crate) fn unindex_reviewer(&mut self, account_id: &AccountId, paper: &PaperMetadata) {
+        let anonymous = self.policies.get(&paper.policy).unwrap().anonymous;
+        self.review_index_remove(ReviewIndex { account_id: account_id.clone(), anonymous, state: None },paper.number);
+        for state in REVIEW_STATES.iter() {
+            self.review_index_remove(ReviewIndex { account_id: account_id.clone(), anonymous, state: Some(*state) },paper.number);
+        }
+    }
+
+    fn review_index_insert(&mut self, key: ReviewIndex, number: u64, token_id: &TokenId) {
+        let mut papers = self.papers_by_reviewer.get(&key).unwrap_or_else(|| {
+            let mut index_hash = CryptoHash::default();
+            index_hash.copy_from_slice(&env::sha256(&key.try_to_vec().unwrap()));
+            TreeMap::new(StorageKey::PapersByReviewerInner { index_hash }.try_to_vec().unwrap())
+        });
+        papers.insert(&number,token_id);
+        self.papers_by_reviewer.insert(&key,&papers);
+    }
+
+    fn review_index_remove(&mut self, key: ReviewIndex, number: u64) {
+        if let Some(mut papers) = self.papers_by_reviewer.get(&key) {
+            if papers.remove(&number).is_some() {
+                self.papers_by_reviewer.insert(&key,&papers);
+            }
+        }
+    }
+
+    // Every status change goes through here to keep `papers_by_status` in sync.
+    pub(crate) fn set_status(&mut self, token_id: &TokenId, paper: &mut PaperMetadata, status: Status) {
+        if let Some(mut papers) = self.papers_by_status.get(&paper.status) {
+            papers.remove(&paper.number);
+            self.papers_by_status.insert(&paper.status,&papers);
+        }
+        paper.status = status;
+        self.status_insert(status,paper.number,token_id);
+        self.index_reviews(token_id,paper);
+    }
+
+    fn status_insert(&mut self, status: Status, number: u64, token_id: &TokenId) {
+        let mut papers = self.papers_by_status.get(&status).unwrap_or_else(|| {
+            TreeMap::new(StorageKey::PapersByStatusInner { status }.try_to_vec().unwrap())
+        });
+        papers.insert(&number,token_id);
+        self.papers_by_status.insert(&status,&papers);
+    }
+}
+
+fn index_insert<K: BorshSerialize>(index: &mut LookupMap<K, UnorderedSet<TokenId>>, key: &K, prefix: StorageKey, token_id: &TokenId) {
+    let mut papers = index.get(key).unwrap_or_else(|| UnorderedSet::new(prefix.try_to_vec().unwrap()));
+    papers.insert(token_id);
+    index.insert(key,&papers);
+}
+
+// Invitations only count as pending, and votes as awaited, while the paper is in review.
+pub(crate) fn review_state(paper: &PaperMetadata, revdata: Option<&Reviewdata>) -> ReviewState {
+    let open = paper.status == Status::Unpublished;
+    match revdata {
+        Some(revdata) if revdata.accept == Approval::AwaitApprov && open => ReviewState::Invited,
+        Some(revdata) if revdata.vote == Vote::NotVoted && open => ReviewState::AwaitingVote,
+        Some(revdata) if revdata.vote != Vote::NotVoted && revdata.payedrev == Pay::NotPayed => ReviewState::Unpaid,
+        _ => ReviewState::Completed,
+    }
+}
+
+pub(crate) fn page(token_ids: &Vector<TokenId>, from_index: Option<U128>, limit: Option<u64>) -> Vec<TokenId> {
+    let start = u128::from(from_index.unwrap_or(U128(0))) as u64;
+    let end = std::cmp::min(token_ids.len(), start.saturating_add(limit.unwrap_or(50)));
+    (start..end).map(|index| token_ids.get(index).unwrap()).collect()
+}
@@ -6,12 +6,8 @@ use near_sdk::{
 //    BorshStorageKey, PromiseOrValue, 
 };
 use std::collections::HashMap;
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap,
-//    UnorderedSet
-};
-use near_sdk::json_types::{Base64VecU8,
-//    U128
-};
+use near_sdk::collections::{LazyOption, LookupMap, TreeMap, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
 
 //use near_contract_standards::non_fungible_token::metadata::{TokenMetadata};//,NFT_METADATA_SPEC,NFTContractMetadata};
 //use near_sdk::json_types::ValidAccountId;
@@ -22,10 +18,35 @@ mod traits;
 pub use crate::view_fns::*;
 mod view_fns;
 
+mod treasury;
+mod editorial;
+mod discussion;
+mod versions;
+mod bibliography;
+mod citations;
+mod rewards;
+mod export;
+mod index;
+mod dashboard;
+mod stats;
+mod reputation;
+mod nft_core;
+mod credits;
+
 const ONE_NEAR: u128 = 1_000_000_000_000_000_000_000_000;
+const REVIEWERS: usize = 3;
+const DEFAULT_REVIEW_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000; // 30 days in nanoseconds
+const MAX_SUMMARY_LEN: usize = 1024;
+const MAX_REFERENCE_LEN: usize = 512;   // URL of a full review report
+const MAX_ABSTRACT_LEN: usize = 4096;
+
+pub const DEFAULT_POLICY: &str = "default";     // Review policy used when `submit` does not name one
+pub const CREDIT_PREFIX: &str = "credit-";      // Token ids of peer review credits, reserved for them
 
 pub const NFT_METADATA_SPEC: &str = "nft-1.0.0"; // This spec can be treated like a version of the standard.
 pub const NFT_STANDARD_NAME: &str = "nep171";    // This is the name of the NFT standard we're using
+pub const JOURNAL_STANDARD_NAME: &str = "nft-researchpaper";   // Standard name of the editorial events
+pub const JOURNAL_EVENT_VERSION: &str = "1.0.0";
 
 //near_sdk::setup_alloc!();
 
@@ -37,6 +58,24 @@ pub struct Contract {
     pub tokenmetadata: UnorderedMap<TokenId, TokenMetadata>,
     pub papersmetadata: UnorderedMap<TokenId, PaperMetadata>,
     pub metadata: LazyOption<NFTContractMetadata>,
+    pub policies: UnorderedMap<String, ReviewPolicy>,
+    pub treasury: Treasury,
+    pub stats: JournalStats,
+    pub editors: UnorderedSet<AccountId>,
+    pub reports: LookupMap<TokenId, Vec<ReviewReport>>,
+    pub discussions: LookupMap<TokenId, Vector<DiscussionMessage>>,
+    pub manuscripts: LookupMap<Vec<u8>, TokenId>,     // sha256 of every submitted version
+    pub cited_by: LookupMap<TokenId, Vector<TokenId>>,
+    pub citation_rewards: LookupMap<AccountId, Balance>,  // claimable citation rewards
+    pub papers_by_status: LookupMap<Status, TreeMap<u64, TokenId>>,   // keyed by submission number
+    pub papers_by_submitter: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub papers_by_author: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub papers_by_reviewer: LookupMap<ReviewIndex, TreeMap<u64, TokenId>>,   // keyed by submission number
+    pub reputations: LookupMap<AccountId, Reputation>,
+    pub credits: UnorderedMap<TokenId, ReviewCredit>,     // soulbound peer review credits
+    pub credits_by_reviewer: LookupMap<AccountId, Vector<TokenId>>,
+    pub view_keys: LookupMap<AccountId, Vec<u8>>,   // sha256 of the secret unlocking views on anonymous reviews
+    pub unclaimed_credits: LookupMap<AccountId, u32>,   // credits for anonymous reviews, minted on claim
 }
 
 #[derive(BorshSerialize)]
@@ -50,6 +89,55 @@ pub enum StorageKey {
     TokensPerType,
     TokensPerTypeInner { token_type_hash: CryptoHash },
     TokenTypesLocked,
+    ReviewPolicies,
+    Editors,
+    ReviewReports,
+    Discussions,
+    DiscussionInner { token_id_hash: CryptoHash },
+    Manuscripts,
+    CitedBy,
+    CitedByInner { token_id_hash: CryptoHash },
+    CitationRewards,
+    PapersByStatus,
+    PapersByStatusInner { status: Status },
+    PapersBySubmitter,
+    PapersBySubmitterInner { account_id_hash: CryptoHash },
+    PapersByAuthor,
+    PapersByAuthorInner { account_id_hash: CryptoHash },
+    PapersByReviewer,
+    PapersByReviewerInner { index_hash: CryptoHash },
+    Reputations,
+    Credits,
+    CreditsByReviewer,
+    CreditsByReviewerInner { account_id_hash: CryptoHash },
+    ViewKeys,
+    UnclaimedCredits,
+}
+
+// Used to build a unique storage prefix for the collections kept per paper.
+pub(crate) fn hash_token_id(token_id: &TokenId) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(token_id.as_bytes()));
+    hash
+}
+
+pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(account_id.as_bytes()));
+    hash
+}
+
+// Fields of a token `extra` as a JSON object. A value that is not an object is kept under "extra".
+pub(crate) fn extra_fields(extra: &Option<String>) -> serde_json::Map<String, serde_json::Value> {
+    match extra.as_deref().map(serde_json::from_str::<serde_json::Value>) {
+        Some(Ok(serde_json::Value::Object(fields))) => fields,
+        Some(_) => {
+            let mut fields = serde_json::Map::new();
+            fields.insert("extra".to_string(), extra.clone().unwrap().into());
+            fields
+        }
+        None => serde_json::Map::new(),
+    }
 }
 
 #[near_bindgen]
@@ -58,7 +146,7 @@ impl Contract {
     #[init]
     pub fn new(metadata: NFTContractMetadata) -> Self {
         assert!(!env::state_exists(), "Already initialized");
-        let papers = Self{
+        let mut papers = Self{
             owner: env::predecessor_account_id(),
             paperid:        LookupMap::new(StorageKey::TokensById.try_to_vec().unwrap()),
             tokenmetadata:  UnorderedMap::new(StorageKey::TokenMetadataById.try_to_vec().unwrap()),
@@ -69,7 +157,26 @@ impl Contract {
 //            metadata:       LazyOption::new(
 //                                StorageKey::NFTContractMetadata.try_to_vec().unwrap()
 //                            ),
+            policies:       UnorderedMap::new(StorageKey::ReviewPolicies.try_to_vec().unwrap()),
+            treasury:       Treasury::default(),
+            stats:          JournalStats::default(),
+            editors:        UnorderedSet::new(StorageKey::Editors.try_to_vec().unwrap()),
+            reports:        LookupMap::new(StorageKey::ReviewReports.try_to_vec().unwrap()),
+            discussions:    LookupMap::new(StorageKey::Discussions.try_to_vec().unwrap()),
+            manuscripts:    LookupMap::new(StorageKey::Manuscripts.try_to_vec().unwrap()),
+            cited_by:       LookupMap::new(StorageKey::CitedBy.try_to_vec().unwrap()),
+            citation_rewards: LookupMap::new(StorageKey::CitationRewards.try_to_vec().unwrap()),
+            papers_by_status:    LookupMap::new(StorageKey::PapersByStatus.try_to_vec().unwrap()),
+            papers_by_submitter: LookupMap::new(StorageKey::PapersBySubmitter.try_to_vec().unwrap()),
+            papers_by_author:    LookupMap::new(StorageKey::PapersByAuthor.try_to_vec().unwrap()),
+            papers_by_reviewer:  LookupMap::new(StorageKey::PapersByReviewer.try_to_vec().unwrap()),
+            reputations:    LookupMap::new(StorageKey::Reputations.try_to_vec().unwrap()),
+            credits:        UnorderedMap::new(StorageKey::Credits.try_to_vec().unwrap()),
+            credits_by_reviewer: LookupMap::new(StorageKey::CreditsByReviewer.try_to_vec().unwrap()),
+            view_keys:      LookupMap::new(StorageKey::ViewKeys.try_to_vec().unwrap()),
+            unclaimed_credits: LookupMap::new(StorageKey::UnclaimedCredits.try_to_vec().unwrap()),
         };
+        papers.policies.insert(&DEFAULT_POLICY.to_string(), &ReviewPolicy::default());
         papers
     }
 
@@ -87,29 +194,31 @@ impl Contract {
             reference_hash: None,
         };
 
-        let papers = Self{
-            owner: env::predecessor_account_id(),
-            paperid:        LookupMap::new(StorageKey::TokensById.try_to_vec().unwrap()),
-            tokenmetadata:  UnorderedMap::new(StorageKey::TokenMetadataById.try_to_vec().unwrap()),
-            papersmetadata: UnorderedMap::new(StorageKey::PaperMetadataById.try_to_vec().unwrap()),
-            metadata:       LazyOption::new(
-                                StorageKey::NFTContractMetadata.try_to_vec().unwrap(),
-                                Some(&metadata)),
-        };
-        papers
+        Self::new(metadata)
     }
 
     #[payable]
+    #[allow(clippy::too_many_arguments)]
     pub fn submit(&mut self, token_id: &TokenId,
-        title: String, author: Vec<String>, accrev: Vec<AccountId>){
+        title: String, author: Vec<String>, accrev: Vec<AccountId>, manuscript: ManuscriptInput, policy: Option<String>,
+        bibliography: Option<Bibliography>){
         assert!(env::attached_deposit()==ONE_NEAR*10,"Should deposit 10 Near.");
-        assert!(accrev.len()==3,"It should be 3 reviewers!");
+        assert!(self.papersmetadata.get(token_id).is_none(),"Paper already submitted");
+        assert!(!token_id.starts_with(CREDIT_PREFIX),"Token ids starting with {} are reserved",CREDIT_PREFIX);
+        assert!(accrev.len()==REVIEWERS,"It should be 3 reviewers!");
+        let policy = policy.unwrap_or_else(|| DEFAULT_POLICY.to_string());
+        let review_policy = self.policies.get(&policy)
+            .unwrap_or_else(|| panic!("Unknown review policy {}", policy));
+        let bibliography = bibliography.unwrap_or_default();
+        Self::check_bibliography(&bibliography,author.len());
+        self.check_references(token_id,&bibliography.references);
         Promise::new(env::current_account_id()).transfer(env::attached_deposit());
         
         let mut rev = HashMap::new();
-        for account_id in accrev {
-            let revdata = Reviewdata{accept: Approval::AwaitApprov, vote: Vote::NotVoted, payedrev: Pay::NotPayed};
+        for (i,account_id) in accrev.into_iter().enumerate() {
+            let revdata = Reviewdata::new(Approval::AwaitApprov,format!("Reviewer {}",i+1));
             assert!(env::signer_account_id() != account_id.clone(),"Signer Cannot be Reviewer");
+            self.check_reputation(&review_policy,&account_id);
             rev.insert(account_id.clone(),revdata);
         }        
 
@@ -121,106 +230,387 @@ impl Contract {
             vote_rev:       0,
             vote_no:        0,
             status:         Status::Unpublished,
+            policy,
+            submitter:      env::predecessor_account_id(),
+            escrow:         U128(env::attached_deposit()),
+            deadline:       U64(env::block_timestamp() + review_policy.review_period.0),
+            invited:        REVIEWERS as u32,
+            round:          1,
+            scores:         review_policy.rubric.iter().map(|criterion| CriterionScore::new(criterion.clone())).collect(),
+            meta_review:    None,
+            appeal:         None,
+            retraction:     None,
+            errata:         Vec::new(),
+            versions:       vec![Self::new_version(manuscript,1)],
+            bibliography,
+            published_at:   None,
+            citation_count: 0,
+            ratings:        Vec::new(),
+            token_overrides: None,
+            number:         self.papersmetadata.len(),
         };
+        self.index_manuscript(token_id,&ppermtdt.versions[0]);
+        self.index_paper(token_id,&ppermtdt);
+        self.stats.submissions += 1;
         self.papersmetadata.insert(&token_id,&ppermtdt);
+        self.hold_escrow(env::attached_deposit());
     }
 
+    #[payable]
     pub fn stataccept(&mut self,token_id: &TokenId,approv: Approval){
         let account_id = env::predecessor_account_id();
         assert!(self.papersmetadata.get(&token_id).unwrap().reviewers.contains_key(&account_id));
         assert!(approv != Approval::AwaitApprov);
 
         let mut a = self.papersmetadata.get(&token_id).unwrap();
-
+        assert!(a.status == Status::Unpublished,"Review closed!");
+        assert!(a.reviewers[&account_id].accept == Approval::AwaitApprov,"Already answered!");
+        assert!(env::block_timestamp() <= a.deadline.0,"Review deadline passed!");
 
         if approv==Approval::Approved{
-            a.reviewers.get_mut(&account_id).unwrap().accept = Approval::Approved;
+            let stake = self.policies.get(&a.policy).unwrap().stake;
+            assert_eq!(env::attached_deposit(),stake.0,"Should stake {} yoctoNEAR.",stake.0);
+            let revdata = a.reviewers.get_mut(&account_id).unwrap();
+            revdata.accept = Approval::Approved;
+            revdata.stake = stake;
+            self.hold_escrow(stake.0);
+            self.update_reputation(&account_id,|reputation| reputation.accepted += 1);
         }else{
+            assert_eq!(env::attached_deposit(),0,"Nothing to stake when declining.");
+            self.update_reputation(&account_id,|reputation| reputation.declined += 1);
             a.reviewers.remove(&account_id);
+            self.unindex_reviewer(&account_id,&a);
         }
+        self.index_reviews(token_id,&a);
         self.papersmetadata.insert(&token_id,&a);
     }
 
+    // Invites a replacement reviewer, e.g. after one declined. The invitation goes through
+    // `stataccept` like the ones sent at submission.
     pub fn addreviewer(&mut self,token_id: &TokenId, accrev: AccountId){
+        assert!(self.is_editor(&env::predecessor_account_id()),"Not an editor!");
         assert!(env::signer_account_id() != accrev.clone(),"Signer Cannot be Reviewer");
         assert!(self.papersmetadata.get(&token_id).unwrap().reviewers.len()<3,"Already Maximum Number of Reviewers");
 
-        let reviewer = Reviewdata{accept: Approval::Approved, vote: Vote::NotVoted, payedrev: Pay::NotPayed};
-        self.papersmetadata.get(&token_id).unwrap().reviewers.insert(accrev.clone(),reviewer);
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert!(a.status == Status::Unpublished,"Review closed!");
+        assert!(accrev != a.submitter,"Submitter Cannot be Reviewer");
+        assert!(!a.reviewers.contains_key(&accrev),"{} is already a reviewer",accrev);
+        self.check_reputation(&self.policies.get(&a.policy).unwrap(),&accrev);
+        a.invited += 1;
+        let reviewer = Reviewdata::new(Approval::AwaitApprov,format!("Reviewer {}",a.invited));
+        a.reviewers.insert(accrev,reviewer);
+        self.index_reviews(token_id,&a);
+        self.papersmetadata.insert(token_id,&a);
     }
 
-    pub fn voting(&mut self,token_id: &TokenId,vote: Vote) {
+    // The reviewer pays for the storage of the report attached to the vote.
+    #[payable]
+    pub fn voting(&mut self,token_id: &TokenId,vote: Vote,report: Option<ReviewReportInput>,scores: Option<Vec<u8>>) {
         assert!(
             self.papersmetadata.get(&token_id).unwrap().reviewers.contains_key(&env::predecessor_account_id()),
             "Not a reviewer!"
         );
         let papmeta = self.papersmetadata.get(&token_id).unwrap();
         let review = papmeta.reviewers.get(&env::predecessor_account_id()).unwrap();
+        assert!(review.accept == Approval::Approved,"Invitation not accepted!");
         assert!(review.vote == Vote::NotVoted,"Already Reviewed!");
+        assert!(env::block_timestamp() <= papmeta.deadline.0,"Voting deadline passed!");
+        assert!(!self.policies.get(&papmeta.policy).unwrap().blind,"Blind review, use commit_vote and reveal_vote!");
 
         let mut a = self.papersmetadata.get(&token_id).unwrap();
+        self.record_vote(token_id,&mut a,&env::predecessor_account_id(),vote,report,scores.unwrap_or_default());
+        self.papersmetadata.insert(&token_id,&a);
+    }
 
-        match vote{
-            Vote::Yes =>    a.vote_yes += 1,
-            Vote::Review => a.vote_rev += 1,
-            Vote::No =>     a.vote_no  += 1,
-            _ => (),
-        }
+    // First phase of a blind review: the reviewer stores the sha256 hash of "<vote>:<salt>",
+    // e.g. the hash of "Yes:8c1f0a", before the voting deadline.
+    pub fn commit_vote(&mut self,token_id: &TokenId,commitment: Base64VecU8) {
+        let account_id = env::predecessor_account_id();
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert!(self.policies.get(&a.policy).unwrap().blind,"Not a blind review!");
+        assert!(a.status == Status::Unpublished,"Review closed!");
+        assert!(env::block_timestamp() <= a.deadline.0,"Commit deadline passed!");
+        assert_eq!(commitment.0.len(),32,"Commitment should be a sha256 hash!");
+
+        let revdata = a.reviewers.get_mut(&account_id).expect("Not a reviewer!");
+        assert!(revdata.accept == Approval::Approved,"Invitation not accepted!");
+        assert!(revdata.commitment.is_none(),"Already committed!");
+        revdata.commitment = Some(commitment);
+        self.papersmetadata.insert(token_id,&a);
+    }
 
-        a.reviewers.get_mut(&env::predecessor_account_id()).unwrap().vote = vote;
-        self.papersmetadata.insert(&token_id,&a);
+    // Second phase of a blind review, open once every reviewer committed or the voting
+    // deadline passed. Only revealed votes are counted.
+    #[payable]
+    pub fn reveal_vote(&mut self,token_id: &TokenId,vote: Vote,salt: String,
+        report: Option<ReviewReportInput>,scores: Option<Vec<u8>>) {
+        let account_id = env::predecessor_account_id();
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        let policy = self.policies.get(&a.policy).unwrap();
+        assert!(policy.blind,"Not a blind review!");
+        assert!(Self::commits_closed(&a),"Commit phase still open!");
+        assert!(env::block_timestamp() <= a.deadline.0 + policy.reveal_period.0,"Reveal deadline passed!");
+        assert!(vote != Vote::NotVoted,"Cannot reveal NotVoted!");
+
+        let revdata = a.reviewers.get(&account_id).expect("Not a reviewer!");
+        assert!(revdata.vote == Vote::NotVoted,"Already Reviewed!");
+        let commitment = revdata.commitment.as_ref().expect("No vote committed!");
+        assert!(
+            env::sha256(format!("{:?}:{}",vote,salt).as_bytes()) == commitment.0,
+            "Vote does not match the commitment!"
+        );
+
+        self.record_vote(token_id,&mut a,&account_id,vote,report,scores.unwrap_or_default());
+        self.papersmetadata.insert(token_id,&a);
     }
 
     #[payable]
     pub fn payreviewer(&mut self,token_id: &TokenId) {
         assert_eq!(&env::predecessor_account_id(),&env::current_account_id(),"Not Owner!");
 
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        self.pay_reviewers(&mut a);
+        self.index_reviews(token_id,&a);
+        self.papersmetadata.insert(token_id,&a);
+    }
 
-        let mut a = self.papersmetadata.get(&token_id).unwrap();
-        for (account_id,revdata) in self.papersmetadata.get(&token_id).unwrap().reviewers.iter_mut() {
+    // Registers a review policy. Papers look their policy up at every step, so a policy cannot
+    // be changed once registered; register a new id instead.
+    pub fn set_policy(&mut self, policy_id: String, policy: ReviewPolicy) {
+        assert_eq!(&env::predecessor_account_id(),&env::current_account_id(),"Not Owner!");
+        assert!(self.policies.get(&policy_id).is_none(),"Policy {} already exists",policy_id);
+        assert!(policy.reject_fee_bps <= 10_000,"Rejection fee above 100%");
+        assert!(policy.max_score >= 1,"Scores need a maximum of at least 1!");
+        assert!(policy.appeal_fee.0 >= ONE_NEAR * REVIEWERS as u128,"Appeal fee should cover the panel rewards!");
+        if let Some(min_average) = policy.min_average {
+            assert!(min_average <= u32::from(policy.max_score) * 100,"Minimum average above the maximum score!");
+        }
+        self.policies.insert(&policy_id,&policy);
+    }
+
+    // Slashes the stake of every reviewer that accepted but did not vote before the deadline
+    // (the reveal deadline for blind reviews). Anyone can call it once the deadline has passed.
+    pub fn slash(&mut self,token_id: &TokenId) {
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert!(a.status == Status::Unpublished,"Review closed!");
+        let policy = self.policies.get(&a.policy).unwrap();
+        assert!(env::block_timestamp() > Self::vote_deadline(&policy,&a),"Voting deadline not reached yet!");
+        let slash_to = policy.slash_to;
+
+        for (account_id,revdata) in a.reviewers.iter_mut() {
+            if revdata.vote != Vote::NotVoted || revdata.stake.0 == 0 {
+                continue;
+            }
+            let reviewer = if policy.anonymous { revdata.handle.clone() } else { account_id.to_string() };
+            env::log_str(&format!("{} slashed {} yoctoNEAR on paper {}",reviewer,revdata.stake.0,token_id));
+            match slash_to {
+                SlashTo::Escrow => a.escrow.0 += revdata.stake.0,
+                SlashTo::Treasury => self.collect_fee(revdata.stake.0),
+            }
+            revdata.stake = U128(0);
+            self.update_reputation(account_id,|reputation| reputation.missed += 1);
+        }
+        self.papersmetadata.insert(token_id,&a);
+    }
+
+    fn record_vote(&mut self, token_id: &TokenId, paper: &mut PaperMetadata, account_id: &AccountId,
+        vote: Vote, report: Option<ReviewReportInput>, scores: Vec<u8>) {
+        assert!(paper.status == Status::Unpublished,"Review closed!");
+        let initial_storage_usage = env::storage_usage();
+        if let Some(report) = report {
+            self.add_report(token_id,paper,account_id,report);
+        }
+        Contract::refund_deposit(env::storage_usage() - initial_storage_usage);
+
+        let policy = self.policies.get(&paper.policy).unwrap();
+        assert_eq!(scores.len(),policy.rubric.len(),"Should score every rubric criterion!");
+        for (score,criterion) in scores.iter().zip(paper.scores.iter_mut()) {
+            assert!(*score >= 1 && *score <= policy.max_score,"Scores go from 1 to {}!",policy.max_score);
+            criterion.total += u32::from(*score);
+            criterion.count += 1;
+            criterion.average = criterion.total * 100 / criterion.count;
+        }
+        paper.reviewers.get_mut(account_id).unwrap().scores = scores;
 
-            match revdata.vote{
-                Vote::NotVoted => continue,
-                _ => (),
-            };
+        match vote{
+            Vote::Yes =>    paper.vote_yes += 1,
+            Vote::Review => paper.vote_rev += 1,
+            Vote::No =>     paper.vote_no  += 1,
+            _ => (),
+        }
+
+        let revdata = paper.reviewers.get_mut(account_id).unwrap();
+        revdata.vote = vote;
+        self.stats.reviews += 1;
+        self.update_reputation(account_id,|reputation| reputation.completed += 1);
+        self.stats.review_turnaround += env::block_timestamp() - revdata.assigned_at.0;
+
+        match policy.payout {
+            Payout::OnVote => self.pay_reviewers(paper),
+            Payout::OnRoundEnd if Self::round_closed(paper) => self.pay_reviewers(paper),
+            _ => (),
+        }
+        if paper.vote_no > 0 && Self::round_closed(paper) {
+            self.finalize_rejected(token_id,paper);
+        }
+        self.index_reviews(token_id,paper);
+    }
 
-            match revdata.payedrev{
-                Pay::NotPayed => (),
-                _ => continue,
-            };
-            
+    fn add_report(&mut self, token_id: &TokenId, paper: &PaperMetadata, account_id: &AccountId, report: ReviewReportInput) {
+        assert!(report.summary.len() <= MAX_SUMMARY_LEN,"Summary longer than {} bytes!",MAX_SUMMARY_LEN);
+        if let Some(reference) = &report.reference {
+            assert!(reference.len() <= MAX_REFERENCE_LEN,"Report reference longer than {} bytes!",MAX_REFERENCE_LEN);
+        }
+        if let Some(hash) = &report.report_hash {
+            assert_eq!(hash.0.len(),32,"Report hash should be a sha256 hash!");
+        }
+        assert!(report.reference.is_none() || report.report_hash.is_some(),"Report reference needs a report_hash!");
+
+        let mut reports = self.reports.get(token_id).unwrap_or_default();
+        reports.push(ReviewReport {
+            reviewer:       account_id.clone(),
+            handle:         paper.reviewers[account_id].handle.clone(),
+            round:          paper.round,
+            summary:        report.summary,
+            reference:      report.reference,
+            report_hash:    report.report_hash,
+            submitted_at:   U64(env::block_timestamp()),
+        });
+        self.reports.insert(token_id,&reports);
+    }
 
-            Promise::new(account_id.clone()).transfer(ONE_NEAR);
+    // Pays every reviewer that accepted, has voted and was not payed yet, giving back their
+    // stake with the reward. The reward comes out of the paper escrow.
+    pub(crate) fn pay_reviewers(&mut self, paper: &mut PaperMetadata) {
+        for (account_id,revdata) in paper.reviewers.iter_mut() {
+            if revdata.accept != Approval::Approved || revdata.vote == Vote::NotVoted || revdata.payedrev == Pay::Payed {
+                continue;
+            }
+            assert!(paper.escrow.0 >= ONE_NEAR,"Not enough escrow to pay {}",account_id);
+            paper.escrow.0 -= ONE_NEAR;
+            Promise::new(account_id.clone()).transfer(ONE_NEAR + revdata.stake.0);
+            self.record_reward(ONE_NEAR, revdata.stake.0);
             revdata.payedrev = Pay::Payed;
-//            a.reviewers.get_mut(&account_id.to_string()).unwrap().payedrev = Pay::Payed;
-            a.reviewers.get_mut(&account_id).unwrap().payedrev = Pay::Payed;
+            revdata.stake = U128(0);
         }
-        self.papersmetadata.insert(&token_id,&a);
     }
 
+    // Closes a rejected paper: reviewers are payed for their work, the policy share of what is
+    // left in escrow goes to the treasury and the remainder is refunded to the submitter.
+    pub(crate) fn finalize_rejected(&mut self, token_id: &TokenId, paper: &mut PaperMetadata) {
+        self.record_outcome(paper,false);
+        self.pay_reviewers(paper);
+        self.return_stakes(paper);
+        self.issue_review_credits(token_id,paper);
+        self.settle_appeal(token_id,paper,false);
+
+        let share = self.policies.get(&paper.policy).unwrap().reject_fee_bps;
+        let fee = paper.escrow.0 / 10_000 * Balance::from(share);
+        let refund = paper.escrow.0 - fee;
+        self.collect_fee(fee);
+        self.record_refund(refund);
+        if refund > 0 {
+            Promise::new(paper.submitter.clone()).transfer(refund);
+        }
+        env::log_str(&format!("Paper {} rejected, {} yoctoNEAR refunded to {}",token_id,refund,paper.submitter));
+
+        paper.escrow = U128(0);
+        self.set_status(token_id,paper,Status::Rejected);
+    }
+
+    // Gives the stake back to reviewers that had no chance to vote because the review was
+    // closed before the deadline.
+    pub(crate) fn return_stakes(&mut self, paper: &mut PaperMetadata) {
+        for (account_id,revdata) in paper.reviewers.iter_mut() {
+            if revdata.vote != Vote::NotVoted || revdata.stake.0 == 0 {
+                continue;
+            }
+            Promise::new(account_id.clone()).transfer(revdata.stake.0);
+            self.record_stake_return(revdata.stake.0);
+            revdata.stake = U128(0);
+        }
+    }
+
+    // Acceptance by the reviewers alone: every vote is Yes and the rubric averages reach the
+    // policy threshold.
+    pub(crate) fn votes_accept(&self, paper: &PaperMetadata) -> bool {
+        let scores_pass = match self.policies.get(&paper.policy).unwrap().min_average {
+            Some(min_average) => paper.scores.iter().all(|criterion| criterion.average >= min_average),
+            None => true,
+        };
+        paper.vote_yes == REVIEWERS as u64 && scores_pass
+    }
+
+    // The round ends once the full panel is seated and every reviewer has voted.
+    fn round_closed(paper: &PaperMetadata) -> bool {
+        paper.reviewers.len() == REVIEWERS
+            && paper.reviewers.values().all(|revdata| revdata.vote != Vote::NotVoted)
+    }
+
+    // Last moment a vote counts: the reveal deadline for blind reviews.
+    pub(crate) fn vote_deadline(policy: &ReviewPolicy, paper: &PaperMetadata) -> u64 {
+        if policy.blind { paper.deadline.0 + policy.reveal_period.0 } else { paper.deadline.0 }
+    }
+
+    fn commits_closed(paper: &PaperMetadata) -> bool {
+        env::block_timestamp() > paper.deadline.0
+            || (paper.reviewers.len() == REVIEWERS
+                && paper.reviewers.values().all(|revdata| revdata.commitment.is_some()))
+    }
+
+    // Votes of a blind review stay hidden until the reveal phase ends.
+    fn votes_hidden(&self, paper: &PaperMetadata) -> bool {
+        let policy = self.policies.get(&paper.policy).unwrap();
+        policy.blind
+            && !Self::round_closed(paper)
+            && env::block_timestamp() <= paper.deadline.0 + policy.reveal_period.0
+    }
+
+    // Mints the paper. The token metadata is derived from the paper; fields set in `overrides`
+    // replace the derived ones, except the media which always is the accepted version.
     #[payable]
     pub fn publish(
         &mut self,
         token_id: TokenId,
         receiver_id: AccountId,
-        token_metadata: TokenMetadata,
+        overrides: Option<TokenMetadata>,
     ){
         assert_eq!(
             env::current_account_id(),
             env::predecessor_account_id(),
             "Only the owner can mint the contract!");
 
-        let a = self.papersmetadata.get(&token_id).unwrap();
-
-        for (acc,revdata) in a.reviewers.iter() {
-            assert!(revdata.payedrev==Pay::Payed,"{} not payed", acc.to_string());
-            assert!(revdata.accept==Approval::Approved,"{} not approved", acc.to_string());
+        let mut a = self.papersmetadata.get(&token_id).unwrap();
+        assert!(a.status == Status::Unpublished || a.status == Status::Accepted,"Paper cannot be published!");
+
+        // an editorial acceptance stands regardless of the raw votes
+        if a.status != Status::Accepted {
+            for (acc,revdata) in a.reviewers.iter() {
+                assert!(revdata.payedrev==Pay::Payed,"{} not payed", acc.to_string());
+                assert!(revdata.accept==Approval::Approved,"{} not approved", acc.to_string());
+            }
+            assert!(a.vote_yes==3,"Not all reviewers have accepted");
+            if let Some(min_average) = self.policies.get(&a.policy).unwrap().min_average {
+                for criterion in a.scores.iter() {
+                    assert!(criterion.average >= min_average,"{} below the acceptance threshold",criterion.criterion);
+                }
+            }
+            self.record_outcome(&a,true);
+            self.issue_review_credits(&token_id,&mut a);
         }
-        assert!(a.vote_yes==3,"Not all reviewers have accepted");
+        let overrides = overrides.or_else(|| a.token_overrides.take());
+        let token_metadata = self.paper_token_metadata(&a,overrides);
         self.mint(token_id.clone(),token_metadata,receiver_id,None);
 
-        let mut a = self.papersmetadata.get(&token_id).unwrap();
-        a.status = Status::Published;
+        self.set_status(&token_id,&mut a,Status::Published);
+        a.published_at = Some(U64(env::block_timestamp()));
+        self.stats.published += 1;
+        self.stats.time_to_publish += env::block_timestamp() - a.versions[0].submitted_at.0;
+        self.record_citations(&token_id,&a.bibliography.references);
+        self.settle_appeal(&token_id,&mut a,true);
+        self.collect_fee(a.escrow.0);
+        a.escrow = U128(0);
         self.papersmetadata.insert(&token_id,&a);
 
     }
@@ -256,6 +646,18 @@ impl Contract {
         
         let initial_storage_usage = env::storage_usage(); //measure the initial storage being used on the contract
 
+        self.internal_mint(token_id,metadata,receiver_id);
+
+        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage; //calculate the required storage which
+                                                                                      //was the used - initial
+
+        Contract::refund_deposit(required_storage_in_bytes); //refund any excess storage if the user attached too much.
+                                                             //Panic if they didn't attach enough to cover the required.
+    }
+
+    // Stores the token and emits the mint event. Storage is paid by the caller of `mint`,
+    // or from the paper escrow for review credits.
+    pub(crate) fn internal_mint(&mut self, token_id: TokenId, metadata: TokenMetadata, receiver_id: AccountId) {
         let royalty = HashMap::new();
         let token = Token {
             owner_id: receiver_id,                    //set the owner ID equal to the receiver ID passed into the function
@@ -281,12 +683,6 @@ impl Contract {
             }]),
         };
         env::log_str(&nft_mint_log.to_string()); // Log the serialized json.
-
-        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage; //calculate the required storage which
-                                                                                      //was the used - initial
-
-        Contract::refund_deposit(required_storage_in_bytes); //refund any excess storage if the user attached too much.
-                                                             //Panic if they didn't attach enough to cover the required.
     }
 }
 
@@ -296,7 +692,7 @@ mod tests {
     use near_sdk::{testing_env,
 //        MockedBlockchain
     };
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
 
     fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
         let mut builder = VMContextBuilder::new();
@@ -356,21 +752,21 @@ mod tests {
                             .build());
 
         cnt.submit(&token_id.to_string(),title.to_string(),author,
-            vec![accounts(2),accounts(3),accounts(4)]
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
         );
 
 
         testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
         cnt.stataccept(&token_id.to_string(),Approval::Approved);
-        cnt.voting(&token_id.to_string(),Vote::Yes);
+        cnt.voting(&token_id.to_string(),Vote::Yes,None,None);
 
         testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).build());
         cnt.stataccept(&token_id.to_string(),Approval::Approved);
-        cnt.voting(&token_id.to_string(),Vote::Yes);
+        cnt.voting(&token_id.to_string(),Vote::Yes,None,None);
 
         testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(4)).build());
         cnt.stataccept(&token_id.to_string(),Approval::Approved);
-        cnt.voting(&token_id.to_string(),Vote::Yes);
+        cnt.voting(&token_id.to_string(),Vote::Yes,None,None);
 
         testing_env!(context.storage_usage(env::storage_usage())
                             .attached_deposit(ONE_NEAR)
@@ -382,7 +778,7 @@ mod tests {
                             .attached_deposit(ONE_NEAR)
                             .predecessor_account_id(accounts(0))
                             .build());
-        cnt.publish(token_id.to_string(), accounts(1), sample_token_metadata());
+        cnt.publish(token_id.to_string(), accounts(1), None);
 
         let a = cnt.papersmetadata.get(&"0".to_string()).unwrap();
         
@@ -415,21 +811,21 @@ mod tests {
                             .build());
 
         cnt.submit(&token_id.to_string(),title.to_string(),author,
-            vec![accounts(2),accounts(3),accounts(4)]
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
         );
                         
 
         testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
         cnt.stataccept(&token_id.to_string(),Approval::Approved);
-        cnt.voting(&token_id.to_string(),Vote::No);
+        cnt.voting(&token_id.to_string(),Vote::No,None,None);
 
         testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).build());
         cnt.stataccept(&token_id.to_string(),Approval::Approved);
-        cnt.voting(&token_id.to_string(),Vote::Yes);
+        cnt.voting(&token_id.to_string(),Vote::Yes,None,None);
 
         testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(4)).build());
         cnt.stataccept(&token_id.to_string(),Approval::Approved);
-        cnt.voting(&token_id.to_string(),Vote::Yes);
+        cnt.voting(&token_id.to_string(),Vote::Yes,None,None);
 
         testing_env!(context.storage_usage(env::storage_usage())
                             .attached_deposit(ONE_NEAR)
@@ -441,7 +837,7 @@ mod tests {
                             .attached_deposit(ONE_NEAR)
                             .predecessor_account_id(accounts(0))
                             .build());
-        cnt.publish(token_id.to_string(), accounts(1), sample_token_metadata());
+        cnt.publish(token_id.to_string(), accounts(1), None);
     }
 
     #[test]
@@ -463,13 +859,1879 @@ mod tests {
                             .build());
 
         cnt.submit(&token_id.to_string(),title.to_string(),author,
-            vec![accounts(2),accounts(3),accounts(4)]
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
         );
                         
 
         testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
         cnt.stataccept(&token_id.to_string(),Approval::NotApproved);
-        cnt.voting(&token_id.to_string(),Vote::Yes);
+        cnt.voting(&token_id.to_string(),Vote::Yes,None,None);
+    }
+
+    #[test]
+    fn payout_on_vote() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("auto".to_string(), ReviewPolicy{payout: Payout::OnVote, ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("auto".to_string()),None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        cnt.voting(&token_id,Vote::Yes,None,None);
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.reviewers.get(accounts(2).as_str()).unwrap().payedrev,Pay::Payed);
+        assert_eq!(a.reviewers.get(accounts(3).as_str()).unwrap().payedrev,Pay::NotPayed);
+    }
+
+    #[test]
+    fn payout_on_round_end() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("round".to_string(), ReviewPolicy{payout: Payout::OnRoundEnd, ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("round".to_string()),None
+        );
+
+        for reviewer in [accounts(2),accounts(3),accounts(4)] {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).build());
+            assert!(cnt.view_paper_meta(&token_id).reviewers.values().all(|r| r.payedrev == Pay::NotPayed));
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,Vote::Yes,None,None);
+        }
+        assert!(cnt.view_paper_meta(&token_id).reviewers.values().all(|r| r.payedrev == Pay::Payed));
+
+        // no manual `payreviewer` call is needed before publishing
+        testing_env!(context.attached_deposit(ONE_NEAR)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.publish(token_id.clone(), accounts(1), None);
+        assert!(cnt.view_paper_meta(&token_id).status==Status::Published);
+    }
+
+    #[test]
+    fn stake_returned_and_slashed() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("staked".to_string(), ReviewPolicy{
+            stake: U128(ONE_NEAR),
+            review_period: U64(1000),
+            slash_to: SlashTo::Escrow,
+            ..Default::default()
+        });
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("staked".to_string()),None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).attached_deposit(ONE_NEAR).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        cnt.voting(&token_id,Vote::Yes,None,None);
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).attached_deposit(ONE_NEAR).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        assert_eq!(cnt.view_paper_meta(&token_id).reviewers[accounts(3).as_str()].stake.0,ONE_NEAR);
+
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .block_timestamp(2000)
+                            .build());
+        cnt.payreviewer(&token_id);
+        cnt.slash(&token_id);
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.reviewers[accounts(2).as_str()].payedrev,Pay::Payed);
+        assert_eq!(a.reviewers[accounts(2).as_str()].stake.0,0);
+        assert_eq!(a.reviewers[accounts(3).as_str()].stake.0,0);
+        assert_eq!(a.escrow.0,ONE_NEAR*10);
+
+        cnt.slash(&token_id);
+        cnt.editor_decision(&token_id,Decision::Reject,Base64VecU8(env::sha256(b"reject")));
+        assert_eq!(cnt.reviewer_reputation(accounts(3)).missed,1);
+    }
+
+    #[test]
+    fn blind_reviewer_not_missed_before_reveal_deadline() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("blind".to_string(), ReviewPolicy{blind: true, review_period: U64(1000), reveal_period: U64(1000), ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("blind".to_string()),None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        cnt.commit_vote(&token_id,commitment("No","salt"));
+
+        // past the commit deadline, but the vote could still be revealed
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .block_timestamp(1500)
+                            .build());
+        cnt.editor_decision(&token_id,Decision::Reject,Base64VecU8(env::sha256(b"reject")));
+        assert_eq!(cnt.reviewer_reputation(accounts(2)).missed,0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Should stake")]
+    fn accept_without_stake() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("staked".to_string(), ReviewPolicy{stake: U128(ONE_NEAR), ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("staked".to_string()),None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invitation not accepted!")]
+    fn vote_without_accepting() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("staked".to_string(), ReviewPolicy{stake: U128(ONE_NEAR), payout: Payout::OnVote, ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("staked".to_string()),None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.voting(&token_id,Vote::Yes,None,None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Review closed!")]
+    fn accept_after_rejection() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("staked".to_string(), ReviewPolicy{stake: U128(ONE_NEAR), ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("staked".to_string()),None
+        );
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(0)).build());
+        cnt.editor_decision(&token_id,Decision::Reject,Base64VecU8(env::sha256(b"out of scope")));
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).attached_deposit(ONE_NEAR).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+    }
+
+    #[test]
+    fn treasury_ledger() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("auto".to_string(), ReviewPolicy{payout: Payout::OnVote, ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("auto".to_string()),None
+        );
+        assert_eq!(cnt.treasury_summary().escrow_outstanding.0,ONE_NEAR*10);
+        assert_eq!(cnt.treasury_summary().available.0,0);
+
+        for reviewer in [accounts(2),accounts(3),accounts(4)] {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,Vote::Yes,None,None);
+        }
+
+        testing_env!(context.attached_deposit(ONE_NEAR)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.publish(token_id, accounts(1), None);
+
+        let summary = cnt.treasury_summary();
+        // the review credits are stored at the expense of the escrow
+        let credits_storage = ONE_NEAR*7 - summary.fees_collected.0;
+        assert!(credits_storage > 0 && credits_storage < ONE_NEAR/10);
+        assert_eq!(summary.rewards_paid.0,ONE_NEAR*3);
+        assert_eq!(summary.escrow_outstanding.0,0);
+        assert_eq!(summary.available.0,summary.fees_collected.0);
+
+        cnt.withdraw_treasury(U128(ONE_NEAR*5),accounts(5));
+        let summary = cnt.treasury_summary();
+        assert_eq!(summary.balance.0,ONE_NEAR*2 - credits_storage);
+        assert_eq!(summary.withdrawn.0,ONE_NEAR*5);
+    }
+
+    #[test]
+    #[should_panic(expected = "can be withdrawn")]
+    fn withdraw_escrow() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"0".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.withdraw_treasury(U128(ONE_NEAR),accounts(0));
+    }
+
+    #[test]
+    fn rejected_paper_refund() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("refund".to_string(), ReviewPolicy{reject_fee_bps: 2_000, ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("refund".to_string()),None
+        );
+
+        for (reviewer,vote) in [(accounts(2),Vote::No),(accounts(3),Vote::Yes),(accounts(4),Vote::Review)] {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,vote,None,None);
+        }
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.status,Status::Rejected);
+        assert_eq!(a.escrow.0,0);
+        assert!(a.reviewers.values().all(|r| r.payedrev == Pay::Payed));
+
+        let summary = cnt.treasury_summary();
+        let credits_storage = ONE_NEAR*7 - summary.fees_collected.0 - summary.refunded.0;
+        assert!(credits_storage > 0 && credits_storage < ONE_NEAR/10);
+        assert_eq!(summary.rewards_paid.0,ONE_NEAR*3);
+        assert_eq!(summary.fees_collected.0,(ONE_NEAR*7 - credits_storage)/10_000*2_000);
+        assert_eq!(summary.escrow_outstanding.0,0);
+    }
+
+    fn manuscript(content: &[u8]) -> ManuscriptInput {
+        ManuscriptInput {
+            media: format!("ipfs://{}",String::from_utf8_lossy(content)),
+            media_hash: Base64VecU8(env::sha256(content)),
+            r#abstract: "Abstract".to_string(),
+        }
+    }
+
+    fn commitment(vote: &str, salt: &str) -> Base64VecU8 {
+        Base64VecU8(env::sha256(format!("{}:{}",vote,salt).as_bytes()))
+    }
+
+    #[test]
+    fn blind_review() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("blind".to_string(), ReviewPolicy{blind: true, ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("blind".to_string()),None
+        );
+
+        for reviewer in [accounts(2),accounts(3),accounts(4)] {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.commit_vote(&token_id,commitment("Yes","salt"));
+        }
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.reveal_vote(&token_id,Vote::Yes,"salt".to_string(),None,None);
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).build());
+        cnt.reveal_vote(&token_id,Vote::Yes,"salt".to_string(),None,None);
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.vote_yes,0);
+        assert!(a.reviewers.values().all(|r| r.vote == Vote::NotVoted));
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(4)).build());
+        cnt.reveal_vote(&token_id,Vote::Yes,"salt".to_string(),None,None);
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.vote_yes,3);
+        assert!(a.reviewers.values().all(|r| r.vote == Vote::Yes));
+    }
+
+    #[test]
+    #[should_panic(expected = "Vote does not match the commitment!")]
+    fn blind_review_wrong_reveal() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("blind".to_string(), ReviewPolicy{blind: true, review_period: U64(1000), ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("blind".to_string()),None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        cnt.commit_vote(&token_id,commitment("No","salt"));
+
+        // the commit deadline passed, so the reveal phase opens without the other commitments
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).block_timestamp(2000).build());
+        cnt.reveal_vote(&token_id,Vote::Yes,"salt".to_string(),None,None);
+    }
+
+    #[test]
+    fn anonymous_reviewers() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("anon".to_string(), ReviewPolicy{anonymous: true, ..Default::default()});
+        cnt.add_editor(accounts(5));
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("anon".to_string()),None
+        );
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert!(a.reviewers.contains_key("Reviewer 2"));
+        assert!(!a.reviewers.contains_key(accounts(3).as_str()));
+        assert!(cnt.reviews_for(accounts(3),None,None,None).is_empty());
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).attached_deposit(ONE_NEAR/10).build());
+        cnt.set_view_key(Base64VecU8(env::sha256(b"reviewer secret")));
+        assert_eq!(cnt.my_reviewer_handle(&token_id,accounts(3),"reviewer secret".to_string()),"Reviewer 2");
+        let reviews = cnt.anonymous_reviews_for(accounts(3),"reviewer secret".to_string(),Some(ReviewState::Invited),None,None);
+        assert_eq!(reviews.len(),1);
+        assert_eq!(reviews[0].handle,Some("Reviewer 2".to_string()));
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(5)).attached_deposit(ONE_NEAR/10).build());
+        cnt.set_view_key(Base64VecU8(env::sha256(b"editor secret")));
+        assert_eq!(cnt.resolve_reviewer(&token_id,"Reviewer 2".to_string(),accounts(5),"editor secret".to_string()),accounts(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrong view key!")]
+    fn reviewer_handle_wrong_view_key() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).attached_deposit(ONE_NEAR/10).build());
+        cnt.set_view_key(Base64VecU8(env::sha256(b"reviewer secret")));
+        cnt.my_reviewer_handle(&token_id,accounts(3),"guess".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Not an editor!")]
+    fn resolve_reviewer_not_editor() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(context.attached_deposit(ONE_NEAR/10).build());
+        cnt.set_view_key(Base64VecU8(env::sha256(b"secret")));
+        cnt.resolve_reviewer(&token_id,"Reviewer 1".to_string(),accounts(1),"secret".to_string());
+    }
+
+    #[test]
+    fn review_report() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).attached_deposit(ONE_NEAR/10).build());
+        cnt.voting(&token_id,Vote::Review,Some(ReviewReportInput{
+            summary: "Section 4 needs a security proof.".to_string(),
+            reference: Some("ipfs://report".to_string()),
+            report_hash: Some(Base64VecU8(env::sha256(b"report"))),
+        }),None);
+
+        let reports = cnt.review_reports(&token_id,Some(1));
+        assert_eq!(reports.len(),1);
+        assert_eq!(reports[0].reviewer,accounts(2).to_string());
+        assert_eq!(reports[0].summary,"Section 4 needs a security proof.");
+        assert!(cnt.review_reports(&token_id,Some(2)).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Report reference needs a report_hash!")]
+    fn review_report_without_hash() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        cnt.voting(&token_id,Vote::Yes,Some(ReviewReportInput{
+            summary: "Fine.".to_string(),
+            reference: Some("ipfs://report".to_string()),
+            report_hash: None,
+        }),None);
+    }
+
+    #[test]
+    #[should_panic(expected = "to cover storage")]
+    fn review_report_without_storage_deposit() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        cnt.voting(&token_id,Vote::Yes,Some(ReviewReportInput{
+            summary: "Fine.".to_string(),
+            reference: None,
+            report_hash: None,
+        }),None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Report reference longer than 512 bytes!")]
+    fn review_report_long_reference() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).attached_deposit(ONE_NEAR/10).build());
+        cnt.voting(&token_id,Vote::Yes,Some(ReviewReportInput{
+            summary: "Fine.".to_string(),
+            reference: Some(format!("ipfs://{}","a".repeat(MAX_REFERENCE_LEN))),
+            report_hash: Some(Base64VecU8(env::sha256(b"report"))),
+        }),None);
+    }
+
+    #[test]
+    fn discussion_thread() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).attached_deposit(ONE_NEAR/10).build());
+        cnt.post_message(&token_id,MessageBody::Text("Where is the proof of Lemma 2?".to_string()));
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(1)).attached_deposit(ONE_NEAR/10).build());
+        cnt.post_message(&token_id,MessageBody::Reference{
+            reference: "ipfs://rebuttal".to_string(),
+            hash: Base64VecU8(env::sha256(b"rebuttal")),
+        });
+
+        let thread = cnt.discussion(&token_id,None,None);
+        assert_eq!(thread.len(),2);
+        assert_eq!(thread[0].role,Role::Reviewer);
+        assert_eq!(thread[1].role,Role::Author);
+        assert_eq!(thread[1].round,1);
+
+        let page = cnt.discussion(&token_id,Some(U128(1)),Some(10));
+        assert_eq!(page.len(),1);
+        assert_eq!(page[0].sender,accounts(1).to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Not allowed to post on this paper!")]
+    fn discussion_outsider() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(5)).build());
+        cnt.post_message(&token_id,MessageBody::Text("Spam".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "to cover storage")]
+    fn discussion_without_storage_deposit() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(1)).build());
+        cnt.post_message(&token_id,MessageBody::Text("Free storage".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Rigour below the acceptance threshold")]
+    fn rubric_threshold() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("rubric".to_string(), ReviewPolicy{
+            payout: Payout::OnVote,
+            rubric: vec!["Novelty".to_string(),"Rigour".to_string()],
+            min_average: Some(350),
+            ..Default::default()
+        });
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("rubric".to_string()),None
+        );
+
+        for (reviewer,scores) in [(accounts(2),vec![5,4]),(accounts(3),vec![4,3]),(accounts(4),vec![4,3])] {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,Vote::Yes,None,Some(scores));
+        }
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.scores[0].average,433);
+        assert_eq!(a.scores[1].average,333);
+        assert_eq!(a.reviewers[accounts(2).as_str()].scores,vec![5,4]);
+
+        testing_env!(context.attached_deposit(ONE_NEAR)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.publish(token_id, accounts(1), None);
+    }
+
+    #[test]
+    fn editor_accepts_over_dissent() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.add_editor(accounts(5));
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        for (reviewer,vote) in [(accounts(2),Vote::Yes),(accounts(3),Vote::Review)] {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,vote,None,None);
+        }
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(5)).build());
+        cnt.editor_decision(&token_id,Decision::Accept,Base64VecU8(env::sha256(b"meta-review")));
+        assert!(get_logs()[0].contains(r#""event":"editor_decision""#));
+        assert!(get_logs()[0].contains(r#""overrides_votes":true"#));
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.status,Status::Accepted);
+        assert!(a.meta_review.unwrap().overrides_votes);
+        assert_eq!(a.reviewers[accounts(3).as_str()].payedrev,Pay::Payed);
+
+        testing_env!(context.attached_deposit(ONE_NEAR)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.publish(token_id.clone(), accounts(1), None);
+        assert_eq!(cnt.view_paper_meta(&token_id).status,Status::Published);
+    }
+
+    #[test]
+    fn editor_rejects() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.editor_decision(&token_id,Decision::Reject,Base64VecU8(env::sha256(b"out of scope")));
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.status,Status::Rejected);
+        assert_eq!(a.meta_review.unwrap().decision,Decision::Reject);
+        assert_eq!(cnt.treasury_summary().escrow_outstanding.0,0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Paper cannot be published!")]
+    fn publish_after_rejection() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        for reviewer in [accounts(2),accounts(3),accounts(4)].iter() {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,Vote::Yes,None,None);
+        }
+
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.payreviewer(&token_id);
+        cnt.editor_decision(&token_id,Decision::Reject,Base64VecU8(env::sha256(b"plagiarism")));
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        cnt.publish(token_id, accounts(1), None);
+    }
+
+    #[test]
+    fn appeal_overturned() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("appeals".to_string(), ReviewPolicy{payout: Payout::OnVote, ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("appeals".to_string()),None
+        );
+
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.editor_decision(&token_id,Decision::Reject,Base64VecU8(env::sha256(b"out of scope")));
+
+        testing_env!(context.attached_deposit(ONE_NEAR*3)
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.appeal(&token_id,"ipfs://appeal".to_string());
+        assert_eq!(cnt.view_paper_meta(&token_id).status,Status::Appealed);
+
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        // the panel is payed from the appeal fee, not from the treasury
+        cnt.withdraw_treasury(cnt.treasury_summary().available,accounts(0));
+        cnt.assign_appeal_panel(&token_id,vec![accounts(5),"frank".parse().unwrap(),"grace".parse().unwrap()]);
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.round,2);
+        assert!(a.reviewers.contains_key("frank"));
+        assert!(!a.reviewers.contains_key(accounts(2).as_str()));
+
+        for reviewer in [accounts(5),"frank".parse().unwrap(),"grace".parse().unwrap()] {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,Vote::Yes,None,None);
+        }
+
+        testing_env!(context.attached_deposit(ONE_NEAR)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.publish(token_id.clone(), accounts(1), None);
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.status,Status::Published);
+        assert_eq!(a.appeal.unwrap().outcome,AppealOutcome::Overturned);
+        let summary = cnt.treasury_summary();
+        assert_eq!(summary.escrow_outstanding.0,0);
+        assert_eq!(summary.refunded.0,ONE_NEAR*7);
+        assert_eq!(summary.rewards_paid.0,ONE_NEAR*3);
+    }
+
+    #[test]
+    fn withdraw_appeal() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(0)).build());
+        cnt.editor_decision(&token_id,Decision::Reject,Base64VecU8(env::sha256(b"out of scope")));
+
+        testing_env!(context.attached_deposit(ONE_NEAR*3).predecessor_account_id(accounts(1)).build());
+        cnt.appeal(&token_id,"ipfs://appeal".to_string());
+        testing_env!(context.attached_deposit(0).build());
+        cnt.withdraw_appeal(&token_id);
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.status,Status::Rejected);
+        assert_eq!(a.appeal.unwrap().outcome,AppealOutcome::Withdrawn);
+        let summary = cnt.treasury_summary();
+        assert_eq!(summary.escrow_outstanding.0,0);
+        assert_eq!(summary.refunded.0,ONE_NEAR*7 + ONE_NEAR*3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only rejected papers can be appealed!")]
+    fn appeal_in_review() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(context.attached_deposit(ONE_NEAR*3).build());
+        cnt.appeal(&token_id,"ipfs://appeal".to_string());
+    }
+
+    #[test]
+    fn retract_and_erratum() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .build());
+        cnt.editor_decision(&token_id,Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        cnt.publish(token_id.clone(), accounts(1), None);
+
+        testing_env!(context.attached_deposit(0).block_timestamp(5_000_000).build());
+        cnt.issue_erratum(&token_id,"ipfs://erratum-1".to_string());
+        cnt.retract(&token_id,Base64VecU8(env::sha256(b"fabricated data")));
+        assert!(get_logs()[1].contains(r#""event":"paper_retraction""#));
+
+        let token = cnt.nft_token(token_id.clone()).unwrap();
+        assert_eq!(token.owner_id,accounts(1));
+        assert_eq!(token.metadata.updated_at,Some(5));
+        let extra: serde_json::Value = serde_json::from_str(&token.metadata.extra.unwrap()).unwrap();
+        assert_eq!(extra["retracted"],true);
+        assert_eq!(extra["errata"][0],"ipfs://erratum-1");
+
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.status,Status::Retracted);
+        assert_eq!(a.errata.len(),1);
+    }
+
+    #[test]
+    fn revise_after_review_votes() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"draft"),None,None
+        );
+
+        for (i,reviewer) in [accounts(2),accounts(3),accounts(4)].iter().enumerate() {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,if i==0 {Vote::Review} else {Vote::Yes},None,None);
+        }
+
+        testing_env!(context.attached_deposit(ONE_NEAR*3)
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.revise(&token_id,manuscript(b"revised"));
+        let a = cnt.view_paper_meta(&token_id);
+        assert_eq!(a.round,2);
+        assert_eq!(a.vote_yes,0);
+        assert_eq!(a.escrow.0,ONE_NEAR*10);
+        assert_eq!(cnt.treasury_summary().rewards_paid.0,ONE_NEAR*3);
+
+        for reviewer in [accounts(2),accounts(3),accounts(4)].iter() {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,Vote::Yes,None,None);
+        }
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(0)).build());
+        cnt.payreviewer(&token_id);
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        cnt.publish(token_id.clone(), accounts(1), None);
+        assert_eq!(cnt.treasury_summary().rewards_paid.0,ONE_NEAR*6);
+
+        let versions = cnt.paper_versions(&token_id);
+        assert_eq!(versions.len(),2);
+        assert_eq!(versions[1].round,2);
+        let token = cnt.nft_token(token_id).unwrap();
+        assert_eq!(token.metadata.media,Some("ipfs://revised".to_string()));
+        assert_eq!(token.metadata.media_hash,Some(Base64VecU8(env::sha256(b"revised"))));
+    }
+
+    #[test]
+    fn revise_stakes_again() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("staked".to_string(), ReviewPolicy{stake: U128(ONE_NEAR), ..Default::default()});
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"draft"),Some("staked".to_string()),None
+        );
+        for (i,reviewer) in [accounts(2),accounts(3),accounts(4)].iter().enumerate() {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).attached_deposit(ONE_NEAR).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,if i==0 {Vote::Review} else {Vote::Yes},None,None);
+        }
+
+        testing_env!(context.attached_deposit(ONE_NEAR*3)
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.revise(&token_id,manuscript(b"revised"));
+        let revdata = &cnt.view_paper_meta(&token_id).reviewers[accounts(2).as_str()];
+        assert_eq!(revdata.accept,Approval::AwaitApprov);
+        assert_eq!(revdata.stake.0,0);
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).attached_deposit(ONE_NEAR).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        assert_eq!(cnt.view_paper_meta(&token_id).reviewers[accounts(2).as_str()].stake.0,ONE_NEAR);
+    }
+
+    #[test]
+    #[should_panic(expected = "Reviewers did not ask for a revision!")]
+    fn revise_while_in_review() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"draft"),None,None
+        );
+        testing_env!(context.attached_deposit(ONE_NEAR*3).build());
+        cnt.revise(&token_id,manuscript(b"revised"));
+    }
+
+    #[test]
+    fn find_by_content_hash() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"0".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        let provenance = cnt.find_by_content_hash(Base64VecU8(env::sha256(b"paper"))).unwrap();
+        assert_eq!(provenance.token_id,"0".to_string());
+        assert_eq!(provenance.submitter,accounts(1));
+        assert_eq!(provenance.round,1);
+        assert!(cnt.find_by_content_hash(Base64VecU8(env::sha256(b"other"))).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Manuscript already submitted by another account!")]
+    fn duplicate_manuscript() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"0".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(context.signer_account_id(accounts(5))
+                            .predecessor_account_id(accounts(5))
+                            .build());
+        cnt.submit(&"1".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+    }
+
+    fn sample_bibliography() -> Bibliography {
+        Bibliography {
+            keywords: vec!["cryptocurrency".to_string(),"peer-to-peer".to_string()],
+            field: Some("cs.CR".to_string()),
+            language: Some("en".to_string()),
+            license: Some("CC-BY-4.0".to_string()),
+            funding: vec![],
+            orcids: vec![Some("0000-0002-1825-0097".to_string())],
+            author_accounts: vec![accounts(1)],
+            references: vec![],
+        }
+    }
+
+    #[test]
+    fn bibliography_in_token_extra() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,Some(sample_bibliography())
+        );
+        assert_eq!(cnt.view_paper_meta(&token_id).bibliography.license,Some("CC-BY-4.0".to_string()));
+
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(0)).build());
+        cnt.editor_decision(&token_id,Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        cnt.publish(token_id.clone(), accounts(1), None);
+
+        let extra: serde_json::Value = serde_json::from_str(&cnt.nft_token(token_id).unwrap().metadata.extra.unwrap()).unwrap();
+        assert_eq!(extra["abstract"],"Abstract");
+        assert_eq!(extra["keywords"][1],"peer-to-peer");
+        assert_eq!(extra["authors"][0]["orcid"],"0000-0002-1825-0097");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid ORCID iD 0000-0002-1825-0098")]
+    fn invalid_orcid() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        let bibliography = Bibliography {
+            orcids: vec![Some("0000-0002-1825-0098".to_string())],
+            ..sample_bibliography()
+        };
+        cnt.submit(&"0".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,Some(bibliography)
+        );
+    }
+
+    #[test]
+    fn publish_derives_token_metadata() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        for (token_id,content) in [("0",b"first"),("1",b"other")].iter() {
+            cnt.submit(&token_id.to_string(),"Title".to_string(),vec!["Author".to_string()],
+                vec![accounts(2),accounts(3),accounts(4)],manuscript(*content),None,None
+            );
+        }
+        for reviewer in [accounts(2),accounts(3),accounts(4)].iter() {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).build());
+            for token_id in ["0","1"].iter() {
+                cnt.stataccept(&token_id.to_string(),Approval::Approved);
+                cnt.voting(&token_id.to_string(),Vote::Yes,None,None);
+            }
+        }
+
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .block_timestamp(7_000_000)
+                            .build());
+        cnt.payreviewer(&"0".to_string());
+        cnt.payreviewer(&"1".to_string());
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        cnt.publish("0".to_string(), accounts(1), None);
+        cnt.publish("1".to_string(), accounts(1), Some(sample_token_metadata()));
+
+        let metadata = cnt.nft_token("0".to_string()).unwrap().metadata;
+        assert_eq!(metadata.title,Some("Title".to_string()));
+        assert_eq!(metadata.description,Some("Abstract".to_string()));
+        assert_eq!(metadata.media,Some("ipfs://first".to_string()));
+        assert_eq!(metadata.issued_at,Some(7));
+        let extra: serde_json::Value = serde_json::from_str(&metadata.extra.unwrap()).unwrap();
+        assert_eq!(extra["review"]["vote_yes"],3);
+        assert_eq!(cnt.view_paper_meta(&"0".to_string()).published_at,Some(U64(7_000_000)));
+
+        let metadata = cnt.nft_token("1".to_string()).unwrap().metadata;
+        assert_eq!(metadata.title,Some("Bitcoin: A Peer-to-Peer Electronic Cash System".to_string()));
+        assert_eq!(metadata.media,Some("ipfs://other".to_string()));
+    }
+
+    #[test]
+    fn editor_token_overrides() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.add_editor(accounts(5));
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(5)).build());
+        cnt.editor_decision(&token_id,Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+        cnt.set_token_overrides(&token_id,sample_token_metadata());
+
+        testing_env!(context.attached_deposit(ONE_NEAR).predecessor_account_id(accounts(0)).build());
+        cnt.publish(token_id.clone(), accounts(1), None);
+
+        let metadata = cnt.nft_token(token_id).unwrap().metadata;
+        assert_eq!(metadata.title,Some("Bitcoin: A Peer-to-Peer Electronic Cash System".to_string()));
+        assert_eq!(metadata.media,Some("ipfs://paper".to_string()));
+    }
+
+    #[test]
+    fn citation_graph() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"0".to_string(),"Cited".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"cited"),None,None
+        );
+        let bibliography = Bibliography {
+            references: vec![Citation::Paper("0".to_string()),Citation::Doi("10.1145/357172.357176".to_string())],
+            ..Default::default()
+        };
+        cnt.submit(&"1".to_string(),"Citing".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"citing"),None,Some(bibliography)
+        );
+        assert!(cnt.cited_by(&"0".to_string(),None,None).is_empty());
+
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(0)).build());
+        cnt.editor_decision(&"1".to_string(),Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        cnt.publish("1".to_string(), accounts(1), None);
+
+        assert_eq!(cnt.cited_by(&"0".to_string(),None,None),vec!["1".to_string()]);
+        assert_eq!(cnt.view_paper_meta(&"0".to_string()).citation_count,1);
+        let references = cnt.references_of(&"1".to_string(),Some(U128(1)),Some(10));
+        assert_eq!(references,vec![Citation::Doi("10.1145/357172.357176".to_string())]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown paper 7 in references")]
+    fn citation_of_unknown_paper() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        let bibliography = Bibliography {
+            references: vec![Citation::Paper("7".to_string())],
+            ..Default::default()
+        };
+        cnt.submit(&"0".to_string(),"Citing".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"citing"),None,Some(bibliography)
+        );
+    }
+
+    #[test]
+    fn citation_rewards() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_citation_reward(U128(ONE_NEAR));
+        testing_env!(context.attached_deposit(ONE_NEAR*3).predecessor_account_id(accounts(5)).build());
+        cnt.donate_citation_pool();
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"0".to_string(),"Cited".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"cited"),None,None
+        );
+        let bibliography = Bibliography {
+            references: vec![Citation::Paper("0".to_string())],
+            ..Default::default()
+        };
+        cnt.submit(&"1".to_string(),"Citing".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"citing"),None,Some(bibliography)
+        );
+
+        testing_env!(context.attached_deposit(0).signer_account_id(accounts(0)).predecessor_account_id(accounts(0)).build());
+        cnt.editor_decision(&"0".to_string(),Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+        cnt.editor_decision(&"1".to_string(),Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        cnt.publish("0".to_string(), accounts(5), None);
+        cnt.publish("1".to_string(), accounts(1), None);
+
+        assert_eq!(cnt.claimable_citation_rewards(accounts(5)).0,ONE_NEAR);
+        let summary = cnt.treasury_summary();
+        assert_eq!(summary.citation_pool.0,ONE_NEAR*2);
+        assert_eq!(summary.citation_owed.0,ONE_NEAR);
+
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(5)).build());
+        cnt.claim_citation_rewards();
+        assert_eq!(cnt.claimable_citation_rewards(accounts(5)).0,0);
+        assert_eq!(cnt.treasury_summary().citation_owed.0,0);
+    }
+
+    #[test]
+    fn bibliographic_export() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Bitcoin: A Peer-to-Peer Electronic Cash System".to_string(),vec!["Nakamoto, Satoshi".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,Some(sample_bibliography())
+        );
+
+        testing_env!(context.attached_deposit(0)
+                            .predecessor_account_id(accounts(0))
+                            .block_timestamp(1_700_000_000_000_000_000)
+                            .build());
+        cnt.editor_decision(&token_id,Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        cnt.publish(token_id.clone(), accounts(1), None);
+
+        let bibtex = cnt.paper_bibtex(&token_id);
+        assert!(bibtex.starts_with("@article{nakamoto2023_0,\n"));
+        assert!(bibtex.contains("  author = {Nakamoto, Satoshi},\n"));
+        assert!(bibtex.contains("  year = {2023},\n  month = {11},\n"));
+
+        let csl = cnt.paper_csl_json(&token_id);
+        assert_eq!(csl.issued.date_parts,vec![vec![2023,11,14]]);
+        assert_eq!(csl.author[0].family,Some("Nakamoto".to_string()));
+        assert_eq!(csl.url,Some("ipfs://paper".to_string()));
+        let json = serde_json::to_value(&csl).unwrap();
+        assert_eq!(json["type"],"article-journal");
+        assert_eq!(json["container-title"],"Bitcoin: A Peer-to-Peer Electronic Cash System");
+    }
+
+    #[test]
+    fn bibtex_special_characters() {
+        assert_eq!(export::bibtex_escape("50% of R&D_costs"),"50\\% of R\\&D\\_costs");
+        assert_eq!(export::bibtex_escape("{a}\\b"),"\\{a\\}\\textbackslash{}b");
+        assert_eq!(export::bibtex_escape("~x^2"),"\\textasciitilde{}x\\textasciicircum{}2");
+        assert_eq!(export::bibtex_url("ipfs://a{b}\\c%d~e"),"ipfs://a%7Bb%7D%5Cc%25d%7Ee");
+        assert_eq!(export::bibtex_url("https://x.org/a%20b?q=1#s"),"https://x.org/a%20b?q=1#s");
+    }
+
+    #[test]
+    fn list_papers_by_status() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        for (token_id,content) in [("0",b"zero"),("1",b"one1"),("2",b"two2")].iter() {
+            cnt.submit(&token_id.to_string(),"Title".to_string(),vec!["Author".to_string()],
+                vec![accounts(2),accounts(3),accounts(4)],manuscript(*content),None,None
+            );
+        }
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(0)).build());
+        cnt.editor_decision(&"1".to_string(),Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+
+        let page = cnt.list_papers(Some(U128(1)),Some(5),None);
+        assert_eq!(page.iter().map(|paper| paper.token_id.clone()).collect::<Vec<_>>(),vec!["1".to_string(),"2".to_string()]);
+        let accepted = cnt.list_papers(None,None,Some(Status::Accepted));
+        assert_eq!(accepted.len(),1);
+        assert_eq!(accepted[0].token_id,"1".to_string());
+        assert_eq!(cnt.list_papers(None,None,Some(Status::Unpublished)).len(),2);
+
+        // a status change between two pages does not shift the next one
+        let first = cnt.list_papers(None,Some(1),Some(Status::Unpublished));
+        assert_eq!(first[0].token_id,"0".to_string());
+        cnt.editor_decision(&"0".to_string(),Decision::Reject,Base64VecU8(env::sha256(b"reject")));
+        let next = cnt.list_papers(Some(U128(u128::from(first[0].number.0) + 1)),Some(1),Some(Status::Unpublished));
+        assert_eq!(next[0].token_id,"2".to_string());
+
+        assert!(cnt.get_paper(&"7".to_string()).is_none());
+        assert_eq!(cnt.get_paper(&"1".to_string()).unwrap().status,Status::Accepted);
+    }
+
+    #[test]
+    fn reviewer_dashboard() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        for (token_id,content) in [("0",b"zero"),("1",b"one1"),("2",b"two2")].iter() {
+            cnt.submit(&token_id.to_string(),"Title".to_string(),vec!["Author".to_string()],
+                vec![accounts(2),accounts(3),accounts(4)],manuscript(*content),None,None
+            );
+        }
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&"1".to_string(),Approval::Approved);
+        cnt.stataccept(&"2".to_string(),Approval::Approved);
+        cnt.voting(&"2".to_string(),Vote::Yes,None,None);
+
+        let states: Vec<ReviewState> = cnt.reviews_for(accounts(2),None,None,None).iter().map(|review| review.state).collect();
+        assert_eq!(states,vec![ReviewState::Invited,ReviewState::AwaitingVote,ReviewState::Unpaid]);
+        let unpaid = cnt.reviews_for(accounts(2),Some(ReviewState::Unpaid),None,None);
+        assert_eq!(unpaid[0].token_id,"2".to_string());
+
+        let second = cnt.reviews_for(accounts(2),None,Some(U128(u128::from(unpaid[0].number.0) - 1)),Some(1));
+        assert_eq!(second[0].token_id,"1".to_string());
+
+        cnt.stataccept(&"0".to_string(),Approval::NotApproved);
+        assert_eq!(cnt.reviews_for(accounts(2),None,None,None).len(),2);
+        assert!(cnt.reviews_for(accounts(2),Some(ReviewState::Invited),None,None).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrong view key!")]
+    fn anonymous_reviews_wrong_view_key() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("anon".to_string(), ReviewPolicy{anonymous: true, ..Default::default()});
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"0".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("anon".to_string()),None
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).attached_deposit(ONE_NEAR/10).build());
+        cnt.set_view_key(Base64VecU8(env::sha256(b"reviewer secret")));
+        cnt.anonymous_reviews_for(accounts(3),"guess".to_string(),None,None,None);
+    }
+
+    #[test]
+    fn author_dashboard() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("blind".to_string(),ReviewPolicy { blind: true, ..Default::default() });
+
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"0".to_string(),"Title".to_string(),vec!["Author".to_string(),"Coauthor".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("blind".to_string()),
+            Some(Bibliography { author_accounts: vec![accounts(5)], ..Default::default() })
+        );
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&"0".to_string(),Approval::Approved);
+        cnt.commit_vote(&"0".to_string(),commitment("Yes","salt"));
+
+        let submissions = cnt.papers_by_submitter(accounts(1),None,None);
+        assert_eq!(submissions.len(),1);
+        assert_eq!(submissions[0].escrow.0,ONE_NEAR*10);
+        let progress = submissions[0].reviewers.iter().find(|reviewer| reviewer.reviewer == accounts(2).to_string()).unwrap();
+        assert!(progress.voted);
+        assert_eq!(progress.vote,None);
+
+        assert_eq!(cnt.papers_by_author_account(accounts(5),None,None)[0].token_id,"0".to_string());
+        assert!(cnt.papers_by_author_account(accounts(1),None,None).is_empty());
+    }
+
+    #[test]
+    fn journal_stats() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("auto".to_string(),ReviewPolicy { payout: Payout::OnVote, ..Default::default() });
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        for (token_id,content) in [("0",b"zero"),("1",b"one1")].iter() {
+            cnt.submit(&token_id.to_string(),"Title".to_string(),vec!["Author".to_string()],
+                vec![accounts(2),accounts(3),accounts(4)],manuscript(*content),Some("auto".to_string()),None
+            );
+        }
+
+        for reviewer in [accounts(2),accounts(3),accounts(4)].iter() {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).block_timestamp(4_000).build());
+            for token_id in ["0","1"].iter() {
+                cnt.stataccept(&token_id.to_string(),Approval::Approved);
+            }
+            cnt.voting(&"0".to_string(),Vote::Yes,None,None);
+            cnt.voting(&"1".to_string(),if *reviewer == accounts(4) {Vote::No} else {Vote::Yes},None,None);
+        }
+
+        testing_env!(context.attached_deposit(ONE_NEAR)
+                            .predecessor_account_id(accounts(0))
+                            .block_timestamp(10_000)
+                            .build());
+        cnt.publish("0".to_string(), accounts(1), None);
+
+        let stats = cnt.journal_stats();
+        assert_eq!(stats.submissions,2);
+        assert_eq!(stats.acceptance_rate_bps,Some(5_000));
+        assert_eq!(stats.avg_time_to_publish,Some(U64(10_000)));
+        assert_eq!(stats.reviews_completed,6);
+        assert_eq!(stats.avg_review_turnaround,Some(U64(4_000)));
+        assert_eq!(stats.rewards_paid.0,ONE_NEAR*6);
+        let rejected = stats.by_status.iter().find(|count| count.status == Status::Rejected).unwrap();
+        assert_eq!(rejected.count,1);
+    }
+
+    #[test]
+    fn reviewer_reputation() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+
+        for reviewer in [accounts(2),accounts(3),accounts(4)].iter() {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,if *reviewer == accounts(4) {Vote::No} else {Vote::Yes},None,None);
+        }
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(1)).build());
+        cnt.rate_review(&token_id,"Reviewer 1".to_string(),5);
+
+        let reputation = cnt.reviewer_reputation(accounts(2));
+        assert_eq!((reputation.accepted,reputation.completed,reputation.disagreed),(1,1,1));
+        assert_eq!(reputation.average_rating,Some(500));
+        assert_eq!(reputation.score,Some(7_500));
+        assert_eq!(cnt.reviewer_reputation(accounts(4)).score,Some(10_000));
+        assert_eq!(cnt.reviewer_reputation(accounts(5)).score,None);
+
+        let ranked = cnt.rank_reviewers(vec![accounts(5),accounts(3),accounts(4)]);
+        assert_eq!(ranked[0].account_id,accounts(4));
+        assert_eq!(ranked[2].account_id,accounts(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "is below the reputation required by the policy")]
+    fn reputation_required_by_policy() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("picky".to_string(),ReviewPolicy { min_reputation: Some(8_000), ..Default::default() });
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"0".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(2)).build());
+        cnt.stataccept(&"0".to_string(),Approval::NotApproved);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        cnt.submit(&"1".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"other"),Some("picky".to_string()),None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Peer review credits cannot be transferred!")]
+    fn review_credits_are_soulbound() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        for reviewer in [accounts(2),accounts(3),accounts(4)].iter() {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,if *reviewer == accounts(4) {Vote::No} else {Vote::Yes},None,None);
+        }
+
+        let credits = cnt.review_credits(accounts(4),None,None);
+        assert_eq!(credits.len(),1);
+        assert_eq!(credits[0].paper_id,Some(token_id.clone()));
+        let credit = cnt.nft_token(credits[0].token_id.clone()).unwrap();
+        assert_eq!(credit.owner_id,accounts(4));
+        assert!(!credit.metadata.extra.unwrap().contains("No"));
+        assert_eq!(cnt.view_paper_meta(&token_id).status,Status::Rejected);
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(4)).build());
+        cnt.nft_transfer(accounts(5),credits[0].token_id.clone(),None,None);
+    }
+
+    #[test]
+    fn anonymous_review_credits() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("anon".to_string(), ReviewPolicy{anonymous: true, ..Default::default()});
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),Some("anon".to_string()),None
+        );
+        for reviewer in [accounts(2),accounts(3),accounts(4)].iter() {
+            testing_env!(VMContextBuilder::new().predecessor_account_id(reviewer.clone()).build());
+            cnt.stataccept(&token_id,Approval::Approved);
+            cnt.voting(&token_id,Vote::Yes,None,None);
+        }
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(0)).build());
+        cnt.editor_decision(&token_id,Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+
+        // nothing is minted along with the decision
+        assert!(cnt.review_credits(accounts(3),None,None).is_empty());
+        assert_eq!(cnt.unclaimed_credits(accounts(3)),1);
+
+        let day = 24 * 60 * 60 * 1_000_000_000;
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3))
+                            .attached_deposit(ONE_NEAR/10)
+                            .block_timestamp(3 * day + 12345)
+                            .build());
+        cnt.claim_review_credits();
+        assert_eq!(cnt.unclaimed_credits(accounts(3)),0);
+
+        let credits = cnt.review_credits(accounts(3),None,None);
+        assert_eq!(credits.len(),1);
+        assert_eq!(credits[0].paper_id,None);
+        assert_eq!(credits[0].round,None);
+        assert_eq!(credits[0].issued_at.0,3 * day);
+        let metadata = cnt.nft_token(credits[0].token_id.clone()).unwrap().metadata;
+        assert_eq!(metadata.description,Some("Anonymous review".to_string()));
+        assert!(!metadata.extra.unwrap().contains("paper"));
+    }
+
+    #[test]
+    #[should_panic(expected = "No review credits to claim!")]
+    fn claim_without_credits() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).build());
+        cnt.claim_review_credits();
+    }
+
+    #[test]
+    #[should_panic(expected = "Token ids starting with credit- are reserved")]
+    fn submit_credit_token_id() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"credit-0".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+    }
+
+    #[test]
+    fn transfer_published_paper() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(context.attached_deposit(0).predecessor_account_id(accounts(0)).build());
+        cnt.editor_decision(&token_id,Decision::Accept,Base64VecU8(env::sha256(b"accept")));
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        cnt.publish(token_id.clone(), accounts(1), None);
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(1)).build());
+        cnt.nft_transfer(accounts(5),token_id.clone(),None,None);
+        assert_eq!(cnt.nft_token(token_id).unwrap().owner_id,accounts(5));
+        assert!(get_logs()[0].contains(r#""event":"nft_transfer""#));
+    }
+
+    #[test]
+    #[should_panic(expected = "Paper already submitted")]
+    fn submit_existing_token_id() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&"0".to_string(),"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(context.signer_account_id(accounts(5))
+                            .predecessor_account_id(accounts(5))
+                            .build());
+        cnt.submit(&"0".to_string(),"Other".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"other"),None,None
+        );
+    }
+
+    #[test]
+    fn addreviewer_sends_invitation() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).build());
+        cnt.stataccept(&token_id,Approval::NotApproved);
+
+        testing_env!(context.attached_deposit(0).signer_account_id(accounts(0)).predecessor_account_id(accounts(0)).build());
+        cnt.addreviewer(&token_id,accounts(5));
+        let reviewer = &cnt.view_paper_meta(&token_id).reviewers[&accounts(5).to_string()];
+        assert_eq!(reviewer.accept,Approval::AwaitApprov);
+        assert_eq!(reviewer.handle,"Reviewer 4");
+
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(5)).build());
+        cnt.stataccept(&token_id,Approval::Approved);
+        assert_eq!(cnt.view_paper_meta(&token_id).reviewers[&accounts(5).to_string()].accept,Approval::Approved);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not an editor!")]
+    fn addreviewer_by_outsider() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).build());
+        cnt.stataccept(&token_id,Approval::NotApproved);
+
+        testing_env!(context.attached_deposit(0).signer_account_id(accounts(5)).predecessor_account_id(accounts(5)).build());
+        cnt.addreviewer(&token_id,accounts(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "is already a reviewer")]
+    fn addreviewer_existing_reviewer() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).build());
+        cnt.stataccept(&token_id,Approval::NotApproved);
+
+        testing_env!(context.attached_deposit(0).signer_account_id(accounts(0)).predecessor_account_id(accounts(0)).build());
+        cnt.addreviewer(&token_id,accounts(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Review closed!")]
+    fn addreviewer_after_decision() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let token_id = "0".to_string();
+        let mut cnt = Contract::new(tokenmeta());
+        testing_env!(context.attached_deposit(ONE_NEAR*10)
+                            .signer_account_id(accounts(1))
+                            .predecessor_account_id(accounts(1))
+                            .build());
+        cnt.submit(&token_id,"Title".to_string(),vec!["Author".to_string()],
+            vec![accounts(2),accounts(3),accounts(4)],manuscript(b"paper"),None,None
+        );
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(3)).build());
+        cnt.stataccept(&token_id,Approval::NotApproved);
+
+        testing_env!(context.attached_deposit(0).signer_account_id(accounts(0)).predecessor_account_id(accounts(0)).build());
+        cnt.editor_decision(&token_id,Decision::Reject,Base64VecU8(env::sha256(b"reject")));
+        cnt.addreviewer(&token_id,accounts(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Policy rubric already exists")]
+    fn set_policy_twice() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("rubric".to_string(),ReviewPolicy { rubric: vec!["Novelty".to_string()], ..Default::default() });
+        cnt.set_policy("rubric".to_string(),ReviewPolicy { rubric: vec!["Clarity".to_string()], ..Default::default() });
+    }
+
+    #[test]
+    #[should_panic(expected = "Minimum average above the maximum score!")]
+    fn set_policy_unreachable_average() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("rubric".to_string(),ReviewPolicy { max_score: 5, min_average: Some(501), ..Default::default() });
+    }
+
+    #[test]
+    #[should_panic(expected = "Scores need a maximum of at least 1!")]
+    fn set_policy_without_scores() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("rubric".to_string(),ReviewPolicy { max_score: 0, ..Default::default() });
+    }
+
+    #[test]
+    #[should_panic(expected = "Rejection fee above 100%")]
+    fn set_policy_fee_above_escrow() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut cnt = Contract::new(tokenmeta());
+        cnt.set_policy("refund".to_string(),ReviewPolicy { reject_fee_bps: 10_001, ..Default::default() });
     }
 
     #[test]
@@ -0,0 +1,41 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    // NEP-171 transfer. Peer review credits are soulbound and refused.
+    #[payable]
+    pub fn nft_transfer(&mut self, receiver_id: AccountId, token_id: TokenId, approval_id: Option<u64>, memo: Option<String>) {
+        near_sdk::assert_one_yocto();
+        assert!(self.credits.get(&token_id).is_none(),"Peer review credits cannot be transferred!");
+        let sender_id = env::predecessor_account_id();
+        let mut token = self.paperid.get(&token_id).expect("No token");
+
+        let authorized_id = if sender_id != token.owner_id {
+            let approved = token.approved_account_ids.get(&sender_id).expect("Unauthorized");
+            if let Some(approval_id) = approval_id {
+                assert_eq!(*approved,approval_id,"The actual approval_id {} is different from the given {}",approved,approval_id);
+            }
+            Some(sender_id.to_string())
+        } else {
+            None
+        };
+        assert_ne!(token.owner_id,receiver_id,"The token owner and the receiver should be different");
+
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id,
+                old_owner_id: token.owner_id.to_string(),
+                new_owner_id: receiver_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+                memo,
+            }]),
+        };
+        env::log_str(&nft_transfer_log.to_string());
+
+        token.owner_id = receiver_id;
+        token.approved_account_ids.clear();
+        self.paperid.insert(&token_id,&token);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::*;
+
+const MAX_RATING: u8 = 5;
+
+#[near_bindgen]
+impl Contract {
+    pub fn reviewer_reputation(&self, account_id: AccountId) -> ReputationView {
+        let reputation = self.reputations.get(&account_id).unwrap_or_default();
+        ReputationView {
+            average_rating: (reputation.ratings_total * 100).checked_div(reputation.ratings_count),
+            score:          reputation_score(&reputation),
+            account_id,
+            accepted:       reputation.accepted,
+            declined:       reputation.declined,
+            completed:      reputation.completed,
+            missed:         reputation.missed,
+            agreed:         reputation.agreed,
+            disagreed:      reputation.disagreed,
+            ratings:        reputation.ratings_count,
+        }
+    }
+
+    // Candidates ordered from the most to the least reliable, those without a track record last.
+    pub fn rank_reviewers(&self, candidates: Vec<AccountId>) -> Vec<ReputationView> {
+        let mut ranked: Vec<ReputationView> = candidates.into_iter().map(|account_id| self.reviewer_reputation(account_id)).collect();
+        ranked.sort_by_key(|reputation| std::cmp::Reverse(reputation.score));
+        ranked
+    }
+
+    // The submitter and the editors rate the review of a reviewer that voted in the current round.
+    pub fn rate_review(&mut self, token_id: &TokenId, reviewer: String, rating: u8) {
+        let rater = env::predecessor_account_id();
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert!(rater == a.submitter || self.is_editor(&rater),"Only the submitter or an editor can rate reviews!");
+        assert!((1..=MAX_RATING).contains(&rating),"Ratings go from 1 to {}!",MAX_RATING);
+
+        // reviewers are named by handle, so that anonymous reviews can be rated too
+        let account_id = a.reviewers.iter()
+            .find(|(_,revdata)| revdata.handle == reviewer)
+            .filter(|(_,revdata)| revdata.vote != Vote::NotVoted)
+            .map(|(account_id,_)| account_id.clone())
+            .expect("No review to rate!");
+        assert!(
+            !a.ratings.iter().any(|r| r.rater == rater && r.reviewer == account_id && r.round == a.round),
+            "Review already rated!"
+        );
+
+        self.update_reputation(&account_id,|reputation| {
+            reputation.ratings_total += u32::from(rating);
+            reputation.ratings_count += 1;
+        });
+        a.ratings.push(ReviewRating { rater, reviewer: account_id, round: a.round, rating });
+        self.papersmetadata.insert(token_id,&a);
+    }
+}
+
+impl Contract {
+    pub(crate) fn update_reputation<F: FnOnce(&mut Reputation)>(&mut self, account_id: &AccountId, update: F) {
+        let mut reputation = self.reputations.get(account_id).unwrap_or_default();
+        update(&mut reputation);
+        self.reputations.insert(account_id,&reputation);
+    }
+
+    // Compares the votes of the last round with the final decision. Reviewers that accepted
+    // but let the deadline pass without voting count as missed, unless `slash` already did.
+    pub(crate) fn record_outcome(&mut self, paper: &PaperMetadata, accepted: bool) {
+        let policy = self.policies.get(&paper.policy).unwrap();
+        let staked = policy.stake.0 > 0;
+        let deadline = Self::vote_deadline(&policy,paper);
+        for (account_id,revdata) in paper.reviewers.iter() {
+            if revdata.accept != Approval::Approved {
+                continue;
+            }
+            match revdata.vote {
+                Vote::Yes if accepted => self.update_reputation(account_id,|reputation| reputation.agreed += 1),
+                Vote::No if !accepted => self.update_reputation(account_id,|reputation| reputation.agreed += 1),
+                Vote::Yes | Vote::No => self.update_reputation(account_id,|reputation| reputation.disagreed += 1),
+                Vote::NotVoted if env::block_timestamp() > deadline && (!staked || revdata.stake.0 > 0) => {
+                    self.update_reputation(account_id,|reputation| reputation.missed += 1)
+                }
+                _ => (),
+            }
+        }
+    }
+
+    pub(crate) fn check_reputation(&self, policy: &ReviewPolicy, account_id: &AccountId) {
+        if let Some(min_reputation) = policy.min_reputation {
+            let score = self.reputations.get(account_id).and_then(|reputation| reputation_score(&reputation));
+            assert!(score.unwrap_or(min_reputation) >= min_reputation,"{} is below the reputation required by the policy",account_id);
+        }
+    }
+}
+
+// Average of the ratios known for a reviewer (acceptance, reliability, agreement, rating),
+// in basis points.
+fn reputation_score(reputation: &Reputation) -> Option<u32> {
+    let ratio = |good: u32, bad: u32| (good * 10_000).checked_div(good + bad);
+    let components: Vec<u32> = [
+        ratio(reputation.accepted,reputation.declined),
+        ratio(reputation.completed,reputation.missed),
+        ratio(reputation.agreed,reputation.disagreed),
+        (reputation.ratings_total * 10_000).checked_div(reputation.ratings_count * u32::from(MAX_RATING)),
+    ].iter().flatten().copied().collect();
+    (components.iter().sum::<u32>()).checked_div(components.len() as u32)
+}
@@ -0,0 +1,55 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    // Amount split among the owners of the cited papers each time a paper is published.
+    pub fn set_citation_reward(&mut self, amount: U128) {
+        assert_eq!(&env::predecessor_account_id(),&env::current_account_id(),"Not Owner!");
+        self.treasury.citation_reward = amount.0;
+    }
+
+    // Moves journal funds into the citation rewards pool.
+    pub fn fund_citation_pool(&mut self, amount: U128) {
+        assert_eq!(&env::predecessor_account_id(),&env::current_account_id(),"Not Owner!");
+        assert!(amount.0 <= self.withdrawable(),"Only {} yoctoNEAR can be moved", self.withdrawable());
+        self.treasury.balance -= amount.0;
+        self.treasury.citation_pool += amount.0;
+    }
+
+    // Anyone can add to the citation rewards pool.
+    #[payable]
+    pub fn donate_citation_pool(&mut self) {
+        assert!(env::attached_deposit() > 0,"Attach the amount to donate!");
+        self.treasury.citation_pool += env::attached_deposit();
+    }
+
+    pub fn claimable_citation_rewards(&self, account_id: AccountId) -> U128 {
+        U128(self.citation_rewards.get(&account_id).unwrap_or(0))
+    }
+
+    pub fn claim_citation_rewards(&mut self) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let amount = self.citation_rewards.remove(&account_id).unwrap_or(0);
+        assert!(amount > 0,"No citation rewards to claim!");
+        self.treasury.citation_owed -= amount;
+        Promise::new(account_id).transfer(amount)
+    }
+}
+
+impl Contract {
+    // Splits the citation reward evenly among the owners of the cited tokens, one share per
+    // cited paper. Whatever cannot be split stays in the pool.
+    pub(crate) fn reward_citations(&mut self, owners: &[AccountId]) {
+        let reward = std::cmp::min(self.treasury.citation_reward, self.treasury.citation_pool);
+        if owners.is_empty() || reward == 0 {
+            return;
+        }
+        let share = reward / owners.len() as u128;
+        for owner in owners {
+            let owed = self.citation_rewards.get(owner).unwrap_or(0);
+            self.citation_rewards.insert(owner,&(owed + share));
+        }
+        self.treasury.citation_pool -= share * owners.len() as u128;
+        self.treasury.citation_owed += share * owners.len() as u128;
+    }
+}
@@ -0,0 +1,34 @@
+use crate::*;
+
+const STATUSES: [Status; 7] = [
+    Status::Unpublished, Status::InReview, Status::Accepted, Status::Published,
+    Status::Rejected, Status::Appealed, Status::Retracted,
+];
+
+#[near_bindgen]
+impl Contract {
+    pub fn journal_stats(&self) -> JournalStatsView {
+        let count = |status: &Status| self.papers_by_status.get(status).map_or(0,|papers| papers.len());
+        let by_status: Vec<StatusCount> = STATUSES.iter()
+            .map(|status| StatusCount { status: *status, count: count(status) })
+            .collect();
+
+        // papers accepted so far, whether published yet or not
+        let accepted = count(&Status::Accepted) + count(&Status::Published) + count(&Status::Retracted);
+        let decided = accepted + count(&Status::Rejected);
+
+        JournalStatsView {
+            submissions:            self.stats.submissions,
+            by_status,
+            acceptance_rate_bps:    (accepted * 10_000).checked_div(decided).map(|rate| rate as u32),
+            avg_time_to_publish:    average(self.stats.time_to_publish,self.stats.published),
+            reviews_completed:      self.stats.reviews,
+            avg_review_turnaround:  average(self.stats.review_turnaround,self.stats.reviews),
+            rewards_paid:           U128(self.treasury.rewards_paid),
+        }
+    }
+}
+
+fn average(total: u64, count: u64) -> Option<U64> {
+    total.checked_div(count).map(U64)
+}
@@ -11,6 +11,520 @@ pub struct PaperMetadata {
     pub vote_rev: u64,
     pub vote_no: u64,
     pub status: Status,
+    pub policy: String,
+    pub escrow: U128,           // part of the submission deposit still held for this paper
+    pub deadline: U64,          // reviewers must vote before this block timestamp (nanoseconds)
+    pub submitter: AccountId,
+    pub invited: u32,           // reviewers invited so far, used to number the reviewer handles
+    pub round: u32,             // review round, starting at 1
+    pub scores: Vec<CriterionScore>,    // aggregated rubric scores of the current round
+    pub meta_review: Option<MetaReview>,
+    pub appeal: Option<Appeal>,
+    pub retraction: Option<Retraction>,
+    pub errata: Vec<Erratum>,
+    pub versions: Vec<PaperVersion>,    // manuscript history, the last one is under review
+    pub bibliography: Bibliography,
+    pub published_at: Option<U64>,
+    pub citation_count: u64,    // published papers of this contract citing this one
+    pub ratings: Vec<ReviewRating>,
+    pub token_overrides: Option<TokenMetadata>, // set by an editor, applied by `publish`
+    pub number: u64,            // position in submission order, the stable key of the listings
+}
+
+// Rating of a review by the submitter or an editor, from 1 to MAX_RATING
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReviewRating {
+    pub rater: AccountId,
+    pub reviewer: AccountId,
+    pub round: u32,
+    pub rating: u8,
+}
+
+// Non-transferable token given to a reviewer once a paper they reviewed is finalised
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReviewCredit {
+    pub token_id: TokenId,
+    pub paper_id: Option<TokenId>,     // None when the paper was reviewed anonymously
+    pub round: Option<u32>,            // None when the paper was reviewed anonymously
+    pub issued_at: U64,                // day of the claim for anonymous reviews
+}
+
+// Track record of a reviewer across papers
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct Reputation {
+    pub accepted: u32,          // invitations accepted
+    pub declined: u32,
+    pub completed: u32,         // votes cast before the deadline
+    pub missed: u32,            // accepted invitations whose deadline passed without a vote
+    pub agreed: u32,            // Yes on accepted papers and No on rejected ones
+    pub disagreed: u32,
+    pub ratings_total: u32,
+    pub ratings_count: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReputationView {
+    pub account_id: AccountId,
+    pub accepted: u32,
+    pub declined: u32,
+    pub completed: u32,
+    pub missed: u32,
+    pub agreed: u32,
+    pub disagreed: u32,
+    pub ratings: u32,
+    pub average_rating: Option<u32>,    // in hundredths
+    pub score: Option<u32>,             // basis points, None without any track record
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Default)]
+#[serde(crate = "near_sdk::serde", default)]
+pub struct Bibliography {
+    pub keywords: Vec<String>,
+    pub field: Option<String>,          // field-of-study classification, e.g. "cs.CR"
+    pub language: Option<String>,       // BCP 47 language tag, e.g. "en"
+    pub license: Option<String>,        // SPDX license identifier, e.g. "CC-BY-4.0"
+    pub funding: Vec<String>,           // funding acknowledgements
+    pub orcids: Vec<Option<String>>,    // ORCID iD of each author, in the order of `author`
+    pub author_accounts: Vec<AccountId>,    // NEAR accounts of the authors, for the author index
+    pub references: Vec<Citation>,
+}
+
+// Published paper as a CSL-JSON item, the format read by Zotero, Mendeley and citeproc
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CslItem {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub title: String,
+    pub author: Vec<CslName>,
+    #[serde(rename = "container-title")]
+    pub container_title: String,
+    pub issued: CslDate,
+    #[serde(rename = "abstract")]
+    pub r#abstract: String,
+    pub keyword: Option<String>,
+    pub language: Option<String>,
+    #[serde(rename = "URL")]
+    pub url: Option<String>,
+    pub note: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CslName {
+    pub family: Option<String>,
+    pub given: Option<String>,
+    pub literal: Option<String>,    // names not written as "Family, Given"
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CslDate {
+    #[serde(rename = "date-parts")]
+    pub date_parts: Vec<Vec<u32>>,  // [[year, month, day]]
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum Citation {
+    Paper(TokenId),     // paper of this contract
+    Doi(String),        // external work, e.g. "10.1145/357172.357176"
+}
+
+// Manuscript sent with `submit` and `revise`
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ManuscriptInput {
+    pub media: String,                  // URL or CID of the manuscript
+    pub media_hash: Base64VecU8,        // Base64-encoded sha256 hash of the manuscript
+    pub r#abstract: String,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaperVersion {
+    pub media: String,
+    pub media_hash: Base64VecU8,
+    pub r#abstract: String,
+    pub submitted_at: U64,
+    pub round: u32,                     // review round that examined this version
+}
+
+// Where a manuscript hash was first submitted
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ManuscriptProvenance {
+    pub token_id: TokenId,
+    pub submitter: AccountId,
+    pub round: u32,
+    pub submitted_at: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Retraction {
+    pub editor: AccountId,
+    pub reason_hash: Base64VecU8,   // Base64-encoded sha256 hash of the retraction notice
+    pub retracted_at: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Erratum {
+    pub editor: AccountId,
+    pub reference: String,          // URL to the erratum
+    pub issued_at: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Appeal {
+    pub reason_reference: String,   // URL to the appeal letter
+    pub fee: U128,
+    pub filed_at: U64,
+    pub round: Option<u32>,         // review round of the appeal panel, once assigned
+    pub outcome: AppealOutcome,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum AppealOutcome {
+    Pending,
+    Upheld,         // the rejection stands
+    Overturned,     // the paper is accepted
+    Withdrawn,      // withdrawn by the submitter before a panel was assigned, the fee is refunded
+}
+
+// Final call of a handling editor, see `editor_decision`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaReview {
+    pub editor: AccountId,
+    pub decision: Decision,
+    pub justification_hash: Base64VecU8,   // Base64-encoded sha256 hash of the meta-review text
+    pub round: u32,
+    pub decided_at: U64,
+    pub overrides_votes: bool,              // the decision differs from what the votes alone give
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Debug)]
+#[derive(PartialEq, Clone, Copy)]
+pub enum Decision {
+    Accept,
+    Reject,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CriterionScore {
+    pub criterion: String,
+    pub total: u32,
+    pub count: u32,
+    pub average: u32,           // in hundredths, 425 is an average of 4.25
+}
+
+impl CriterionScore {
+    pub fn new(criterion: String) -> Self {
+        Self { criterion, total: 0, count: 0, average: 0 }
+    }
+}
+
+// Public view of a paper. Blind votes stay hidden until the reveal phase ends and
+// anonymous policies key the reviewers by handle instead of account.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaperView {
+    pub title: String,
+    pub author: Vec<String>,
+    pub reviewers: HashMap<String,Reviewdata>,
+    pub vote_yes: u64,
+    pub vote_rev: u64,
+    pub vote_no: u64,
+    pub status: Status,
+    pub policy: String,
+    pub escrow: U128,
+    pub deadline: U64,
+    pub submitter: AccountId,
+    pub round: u32,
+    pub scores: Vec<CriterionScore>,
+    pub meta_review: Option<MetaReview>,
+    pub appeal: Option<Appeal>,
+    pub retraction: Option<Retraction>,
+    pub errata: Vec<Erratum>,
+    pub bibliography: Bibliography,
+    pub published_at: Option<U64>,
+    pub citation_count: u64,
+}
+
+// One line of `list_papers`
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaperSummary {
+    pub token_id: TokenId,
+    pub number: U64,
+    pub title: String,
+    pub author: Vec<String>,
+    pub status: Status,
+    pub submitter: AccountId,
+    pub round: u32,
+    pub policy: String,
+    pub published_at: Option<U64>,
+    pub citation_count: u64,
+}
+
+// A paper on the dashboard of a reviewer
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReviewAssignment {
+    pub token_id: TokenId,
+    pub number: U64,                // submission number of the paper, the paging cursor
+    pub title: String,
+    pub paper_status: Status,
+    pub round: u32,
+    pub deadline: U64,
+    pub state: ReviewState,
+    pub handle: Option<String>,     // None once the reviewer left the panel
+    pub stake: U128,
+    pub committed: bool,            // blind vote committed, waiting for the reveal
+}
+
+// A paper on the dashboard of its submitter or authors
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SubmissionView {
+    pub token_id: TokenId,
+    pub title: String,
+    pub status: Status,
+    pub round: u32,
+    pub deadline: U64,
+    pub escrow: U128,               // part of the submission deposit still held
+    pub reviewers: Vec<ReviewerProgress>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReviewerProgress {
+    pub reviewer: String,           // handle for anonymous policies
+    pub accept: Approval,
+    pub voted: bool,                // vote cast or, in blind reviews, committed
+    pub vote: Option<Vote>,         // None until cast, and while blind votes are hidden
+    pub payed: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum ReviewState {
+    Invited,        // invitation not answered yet
+    AwaitingVote,   // invitation accepted, vote not cast
+    Unpaid,         // vote cast, reward not payed yet
+    Completed,      // nothing left to do
+}
+
+// Key of the review index: the papers of a reviewer, split by the anonymity of their policy
+// and, unless `state` is None, by the state of the review
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ReviewIndex {
+    pub account_id: AccountId,
+    pub anonymous: bool,
+    pub state: Option<ReviewState>,
+}
+
+// Report a reviewer attaches to a vote: a short summary on-chain and optionally the full
+// report off-chain, pinned by its hash.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReviewReportInput {
+    pub summary: String,
+    pub reference: Option<String>,          // URL to the full report
+    pub report_hash: Option<Base64VecU8>,   // Base64-encoded sha256 hash of the report. Required if `reference` is included.
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ReviewReport {
+    pub reviewer: AccountId,
+    pub handle: String,
+    pub round: u32,
+    pub summary: String,
+    pub reference: Option<String>,
+    pub report_hash: Option<Base64VecU8>,
+    pub submitted_at: U64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReviewReportView {
+    pub reviewer: String,                   // account, or handle for anonymous policies
+    pub round: u32,
+    pub summary: String,
+    pub reference: Option<String>,
+    pub report_hash: Option<Base64VecU8>,
+    pub submitted_at: U64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum Role {
+    Author,
+    Reviewer,
+    Editor,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Debug)]
+pub enum MessageBody {
+    Text(String),
+    Reference { reference: String, hash: Base64VecU8 },  // off-chain message pinned by its sha256 hash
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct DiscussionMessage {
+    pub sender: AccountId,
+    pub handle: Option<String>,     // reviewer handle, shown instead of `sender` by anonymous policies
+    pub role: Role,
+    pub round: u32,
+    pub posted_at: U64,
+    pub body: MessageBody,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DiscussionMessageView {
+    pub sender: String,
+    pub role: Role,
+    pub round: u32,
+    pub posted_at: U64,
+    pub body: MessageBody,
+}
+
+// Running aggregates behind `journal_stats`
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct JournalStats {
+    pub submissions: u64,
+    pub published: u64,
+    pub time_to_publish: u64,       // sum over published papers, nanoseconds
+    pub reviews: u64,               // votes cast
+    pub review_turnaround: u64,     // sum over votes of the time since assignment, nanoseconds
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JournalStatsView {
+    pub submissions: u64,
+    pub by_status: Vec<StatusCount>,
+    pub acceptance_rate_bps: Option<u32>,       // accepted / (accepted + rejected), in basis points
+    pub avg_time_to_publish: Option<U64>,       // nanoseconds
+    pub reviews_completed: u64,
+    pub avg_review_turnaround: Option<U64>,     // nanoseconds
+    pub rewards_paid: U128,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StatusCount {
+    pub status: Status,
+    pub count: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct Treasury {
+    pub balance: Balance,               // journal funds not yet withdrawn
+    pub fees_collected: Balance,        // everything ever released from escrow to the journal
+    pub rewards_paid: Balance,          // rewards transferred to reviewers
+    pub refunded: Balance,              // escrow given back to submitters of rejected papers
+    pub escrow_outstanding: Balance,    // paper escrows and reviewer stakes still held
+    pub withdrawn: Balance,             // taken out by the owner with `withdraw_treasury`
+    pub citation_reward: Balance,       // split among the cited papers each time a paper is published
+    pub citation_pool: Balance,         // funds set aside for citation rewards
+    pub citation_owed: Balance,         // citation rewards credited but not claimed yet
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TreasurySummary {
+    pub balance: U128,
+    pub fees_collected: U128,
+    pub rewards_paid: U128,
+    pub refunded: U128,
+    pub escrow_outstanding: U128,
+    pub storage_locked: U128,
+    pub withdrawn: U128,
+    pub available: U128,                // what `withdraw_treasury` accepts right now
+    pub citation_reward: U128,
+    pub citation_pool: U128,
+    pub citation_owed: U128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReviewPolicy {
+    pub payout: Payout,     // when reviewers get their reward
+    pub stake: U128,        // deposit a reviewer must attach to `stataccept`, 0 for none
+    pub review_period: U64, // nanoseconds from submission until the voting deadline
+    pub slash_to: SlashTo,  // where the stake of a reviewer that missed the deadline goes
+    pub reject_fee_bps: u32,    // basis points of the escrow left after rewards kept when a paper is rejected
+    pub blind: bool,        // reviewers commit a hash of their vote and reveal it later
+    pub reveal_period: U64, // nanoseconds after the voting deadline to reveal blind votes
+    pub anonymous: bool,    // public views show "Reviewer N" handles instead of accounts, the raw state still holds them
+    pub rubric: Vec<String>,        // criteria reviewers score, e.g. novelty, rigour, clarity
+    pub max_score: u8,              // scores go from 1 to `max_score`
+    pub min_average: Option<u32>,   // every criterion average (in hundredths) must reach it to publish
+    pub appeal_fee: U128,           // deposit to appeal a rejection, pays the appeal panel
+    pub min_reputation: Option<u32>,    // reviewers with a lower reputation score (basis points) cannot be invited
+}
+
+impl Default for ReviewPolicy {
+    fn default() -> Self {
+        Self {
+            payout: Payout::Manual,
+            stake: U128(0),
+            review_period: U64(DEFAULT_REVIEW_PERIOD),
+            slash_to: SlashTo::Treasury,
+            reject_fee_bps: 3_000,
+            blind: false,
+            reveal_period: U64(DEFAULT_REVIEW_PERIOD),
+            anonymous: false,
+            rubric: Vec::new(),
+            max_score: 5,
+            min_average: None,
+            appeal_fee: U128(ONE_NEAR * REVIEWERS as u128),
+            min_reputation: None,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum Payout {
+    Manual,         // the owner calls `payreviewer`
+    OnVote,         // each reviewer is payed as soon as the vote is cast
+    OnRoundEnd,     // all reviewers are payed once the last vote of the round is cast
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum SlashTo {
+    Escrow,         // added to the escrow of the paper
+    Treasury,       // kept by the journal
 }
 
 
@@ -29,10 +543,15 @@ pub enum Vote {
 #[serde(crate = "near_sdk::serde")]
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[derive(Clone, Copy)]
 pub enum Status {
     Published,
     InReview,
     Unpublished,
+    Rejected,
+    Accepted,       // accepted by an editor, ready to publish
+    Appealed,       // rejection appealed, waiting for an editor to pick a new panel
+    Retracted,      // published, then retracted by an editor; the NFT is kept
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
@@ -61,6 +580,26 @@ pub struct Reviewdata {
     pub accept: Approval,
     pub vote: Vote,
     pub payedrev: Pay,
+    pub stake: U128,
+    pub commitment: Option<Base64VecU8>,    // sha256 of "<vote>:<salt>" in blind reviews
+    pub handle: String,                     // pseudonym shown by anonymous policies, e.g. "Reviewer 2"
+    pub scores: Vec<u8>,                    // one per rubric criterion
+    pub assigned_at: U64,                   // invitation, or start of the revision round being reviewed
+}
+
+impl Reviewdata {
+    pub fn new(accept: Approval, handle: String) -> Self {
+        Self {
+            accept,
+            handle,
+            vote: Vote::NotVoted,
+            payedrev: Pay::NotPayed,
+            stake: U128(0),
+            commitment: None,
+            scores: Vec::new(),
+            assigned_at: U64(env::block_timestamp()),
+        }
+    }
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -92,6 +631,15 @@ pub struct NFTContractMetadata {
     pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonToken {
+    pub token_id: TokenId,
+    pub owner_id: AccountId,
+    pub metadata: TokenMetadata,
+    pub approved_account_ids: HashMap<AccountId, u64>,
+}
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct Token {
     pub owner_id: AccountId,                           //owner of the token
@@ -138,6 +686,9 @@ impl fmt::Display for EventLog {
 pub enum EventLogVariant {
     NftMint(Vec<NftMintLog>),
     NftTransfer(Vec<NftTransferLog>),
+    EditorDecision(Vec<EditorDecisionLog>),
+    PaperRetraction(Vec<PaperRetractionLog>),
+    PaperErratum(Vec<PaperErratumLog>),
 }
 
 
@@ -178,3 +729,47 @@ pub struct NftTransferLog {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
 }
+
+/// An event log to capture an editor decision on a paper
+///
+/// Arguments
+/// * `token_id`: "0"
+/// * `editor_id`: "editor.near"
+/// * `decision`: "Accept" or "Reject"
+/// * `overrides_votes`: true when the decision differs from the reviewer votes
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EditorDecisionLog {
+    pub token_id: String,
+    pub editor_id: String,
+    pub decision: Decision,
+    pub overrides_votes: bool,
+}
+
+/// An event log to capture the retraction of a published paper
+///
+/// Arguments
+/// * `token_id`: "0"
+/// * `editor_id`: "editor.near"
+/// * `reason_hash`: sha256 hash of the retraction notice
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaperRetractionLog {
+    pub token_id: String,
+    pub editor_id: String,
+    pub reason_hash: Base64VecU8,
+}
+
+/// An event log to capture an erratum to a published paper
+///
+/// Arguments
+/// * `token_id`: "0"
+/// * `editor_id`: "editor.near"
+/// * `reference`: URL to the erratum
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaperErratumLog {
+    pub token_id: String,
+    pub editor_id: String,
+    pub reference: String,
+}
@@ -0,0 +1,85 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    pub fn treasury_summary(&self) -> TreasurySummary {
+        TreasurySummary {
+            balance:            U128(self.treasury.balance),
+            fees_collected:     U128(self.treasury.fees_collected),
+            rewards_paid:       U128(self.treasury.rewards_paid),
+            refunded:           U128(self.treasury.refunded),
+            escrow_outstanding: U128(self.treasury.escrow_outstanding),
+            storage_locked:     U128(Self::storage_locked()),
+            withdrawn:          U128(self.treasury.withdrawn),
+            available:          U128(self.withdrawable()),
+            citation_reward:    U128(self.treasury.citation_reward),
+            citation_pool:      U128(self.treasury.citation_pool),
+            citation_owed:      U128(self.treasury.citation_owed),
+        }
+    }
+
+    pub fn withdraw_treasury(&mut self, amount: U128, receiver: AccountId) -> Promise {
+        assert_eq!(&env::predecessor_account_id(),&env::current_account_id(),"Not Owner!");
+        assert!(amount.0 <= self.withdrawable(),"Only {} yoctoNEAR can be withdrawn", self.withdrawable());
+
+        self.treasury.balance -= amount.0;
+        self.treasury.withdrawn += amount.0;
+        Promise::new(receiver).transfer(amount.0)
+    }
+}
+
+impl Contract {
+    // Funds the owner may take out: the journal share, capped by what the account holds
+    // beyond escrows, stakes, citation rewards and the storage reserve.
+    pub(crate) fn withdrawable(&self) -> Balance {
+        let reserved = self.treasury.escrow_outstanding
+            + self.treasury.citation_pool
+            + self.treasury.citation_owed
+            + Self::storage_locked();
+        let free = env::account_balance().saturating_sub(reserved);
+        std::cmp::min(self.treasury.balance, free)
+    }
+
+    fn storage_locked() -> Balance {
+        env::storage_byte_cost() * Balance::from(env::storage_usage())
+    }
+
+    // Money coming into the contract that is still owed to a paper or a reviewer.
+    pub(crate) fn hold_escrow(&mut self, amount: Balance) {
+        self.treasury.escrow_outstanding += amount;
+    }
+
+    // Money held in escrow that now belongs to the journal.
+    pub(crate) fn collect_fee(&mut self, amount: Balance) {
+        self.treasury.escrow_outstanding -= amount;
+        self.treasury.fees_collected += amount;
+        self.treasury.balance += amount;
+    }
+
+    // A reward payed out of escrow, with the reviewer stake given back alongside it.
+    pub(crate) fn record_reward(&mut self, reward: Balance, stake: Balance) {
+        self.treasury.escrow_outstanding -= reward + stake;
+        self.treasury.rewards_paid += reward;
+    }
+
+    // Stake given back to a reviewer without a reward.
+    pub(crate) fn record_stake_return(&mut self, stake: Balance) {
+        self.treasury.escrow_outstanding -= stake;
+    }
+
+    // Escrow given back to the submitter of a rejected paper.
+    pub(crate) fn record_refund(&mut self, amount: Balance) {
+        self.treasury.escrow_outstanding -= amount;
+        self.treasury.refunded += amount;
+    }
+
+    // Storage a paper leaves behind, e.g. its review credits, is payed from its escrow. The
+    // journal covers what the escrow cannot, e.g. when an appeal fee all went to the panel.
+    pub(crate) fn pay_storage(&mut self, paper: &mut PaperMetadata, storage_used: u64) {
+        let cost = env::storage_byte_cost() * Balance::from(storage_used);
+        let from_escrow = std::cmp::min(cost, paper.escrow.0);
+        paper.escrow.0 -= from_escrow;
+        self.treasury.escrow_outstanding -= from_escrow;
+        self.treasury.balance = self.treasury.balance.saturating_sub(cost - from_escrow);
+    }
+}
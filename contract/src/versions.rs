@@ -0,0 +1,85 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    // Uploads a new version of the manuscript once a round closed with revision requests.
+    // The reviewers of that round examine the new version in a fresh round, paid by the
+    // attached deposit.
+    #[payable]
+    pub fn revise(&mut self, token_id: &TokenId, manuscript: ManuscriptInput) {
+        let mut a = self.papersmetadata.get(token_id).unwrap();
+        assert_eq!(env::predecessor_account_id(),a.submitter,"Only the submitter can revise!");
+        assert!(a.status == Status::Unpublished,"Review closed!");
+        assert!(Self::round_closed(&a) && a.vote_rev > 0,"Reviewers did not ask for a revision!");
+        assert!(env::attached_deposit()==ONE_NEAR*REVIEWERS as u128,"Should deposit {} Near.",REVIEWERS);
+
+        // the previous round is settled before its votes are cleared, and reviewers accept the
+        // new round through `stataccept`, staking again when the policy asks for it
+        self.pay_reviewers(&mut a);
+        for revdata in a.reviewers.values_mut() {
+            revdata.accept = Approval::AwaitApprov;
+            revdata.vote = Vote::NotVoted;
+            revdata.payedrev = Pay::NotPayed;
+            revdata.commitment = None;
+            revdata.scores = Vec::new();
+            revdata.assigned_at = U64(env::block_timestamp());
+        }
+        let policy = self.policies.get(&a.policy).unwrap();
+        a.vote_yes = 0;
+        a.vote_rev = 0;
+        a.vote_no = 0;
+        a.scores = policy.rubric.into_iter().map(CriterionScore::new).collect();
+        a.round += 1;
+        a.deadline = U64(env::block_timestamp() + policy.review_period.0);
+        a.escrow.0 += env::attached_deposit();
+        let version = Self::new_version(manuscript,a.round);
+        self.index_manuscript(token_id,&version);
+        a.versions.push(version);
+        self.hold_escrow(env::attached_deposit());
+        self.index_reviews(token_id,&a);
+        self.papersmetadata.insert(token_id,&a);
+    }
+
+    pub fn paper_versions(&self, token_id: &TokenId) -> Vec<PaperVersion> {
+        self.papersmetadata.get(token_id).unwrap().versions
+    }
+
+    pub fn find_by_content_hash(&self, media_hash: Base64VecU8) -> Option<ManuscriptProvenance> {
+        let token_id = self.manuscripts.get(&media_hash.0)?;
+        let a = self.papersmetadata.get(&token_id).unwrap();
+        let version = a.versions.iter().find(|version| version.media_hash == media_hash).unwrap();
+        Some(ManuscriptProvenance {
+            token_id,
+            submitter:      a.submitter,
+            round:          version.round,
+            submitted_at:   version.submitted_at,
+        })
+    }
+}
+
+impl Contract {
+    pub(crate) fn new_version(manuscript: ManuscriptInput, round: u32) -> PaperVersion {
+        assert!(!manuscript.media.is_empty(),"Manuscript media is missing!");
+        assert_eq!(manuscript.media_hash.0.len(),32,"Manuscript hash should be a sha256 hash!");
+        assert!(manuscript.r#abstract.len() <= MAX_ABSTRACT_LEN,"Abstract is too long!");
+        PaperVersion {
+            media:          manuscript.media,
+            media_hash:     manuscript.media_hash,
+            r#abstract:     manuscript.r#abstract,
+            submitted_at:   U64(env::block_timestamp()),
+            round,
+        }
+    }
+
+    // A manuscript can back a single paper. Its submitter can only send it again as a
+    // revision of that paper, anyone else is refused.
+    pub(crate) fn index_manuscript(&mut self, token_id: &TokenId, version: &PaperVersion) {
+        if let Some(existing) = self.manuscripts.get(&version.media_hash.0) {
+            assert!(&existing != token_id,"This manuscript was already submitted for this paper!");
+            let submitter = self.papersmetadata.get(&existing).unwrap().submitter;
+            assert_eq!(submitter,env::predecessor_account_id(),"Manuscript already submitted by another account!");
+            panic!("Manuscript already submitted as paper {}, send a revision instead!",existing);
+        }
+        self.manuscripts.insert(&version.media_hash.0,token_id);
+    }
+}
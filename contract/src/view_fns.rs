@@ -9,7 +9,85 @@ impl Contract {
         }
         papers
     }
-    pub fn view_paper_meta(&self,token_id: &TokenId) -> PaperMetadata{
-        self.papersmetadata.get(token_id).unwrap()
+    pub fn view_paper_meta(&self,token_id: &TokenId) -> PaperView{
+        self.paper_view(self.papersmetadata.get(token_id).unwrap())
+    }
+    // Review reports of a paper, optionally only those of one round. Reports stay hidden
+    // while the votes of a blind review are.
+    pub fn review_reports(&self,token_id: &TokenId,round: Option<u32>) -> Vec<ReviewReportView>{
+        let paper = self.papersmetadata.get(token_id).unwrap();
+        if self.votes_hidden(&paper) {
+            return Vec::new();
+        }
+        let anonymous = self.policies.get(&paper.policy).unwrap().anonymous;
+
+        self.reports.get(token_id).unwrap_or_default().into_iter()
+            .filter(|report| round.is_none() || round == Some(report.round))
+            .map(|report| ReviewReportView {
+                reviewer:       if anonymous { report.handle } else { report.reviewer.to_string() },
+                round:          report.round,
+                summary:        report.summary,
+                reference:      report.reference,
+                report_hash:    report.report_hash,
+                submitted_at:   report.submitted_at,
+            })
+            .collect()
+    }
+    // NEP-171 token view. Retractions and errata show up in `metadata.extra`.
+    pub fn nft_token(&self,token_id: TokenId) -> Option<JsonToken>{
+        let token = self.paperid.get(&token_id)?;
+        let metadata = self.tokenmetadata.get(&token_id).unwrap();
+        Some(JsonToken {
+            token_id,
+            owner_id: token.owner_id,
+            metadata,
+            approved_account_ids: token.approved_account_ids,
+        })
+    }
+    pub fn view_policy(&self,policy_id: String) -> Option<ReviewPolicy>{
+        self.policies.get(&policy_id)
+    }
+}
+
+impl Contract {
+    pub(crate) fn paper_view(&self, paper: PaperMetadata) -> PaperView {
+        let hidden = self.votes_hidden(&paper);
+        let anonymous = self.policies.get(&paper.policy).unwrap().anonymous;
+
+        let reviewers = paper.reviewers.into_iter().map(|(account_id,mut revdata)| {
+            if hidden {
+                revdata.vote = Vote::NotVoted;
+                revdata.scores.clear();
+            }
+            let key = if anonymous { revdata.handle.clone() } else { account_id.to_string() };
+            (key,revdata)
+        }).collect();
+
+        PaperView {
+            title:      paper.title,
+            author:     paper.author,
+            reviewers,
+            vote_yes:   if hidden { 0 } else { paper.vote_yes },
+            vote_rev:   if hidden { 0 } else { paper.vote_rev },
+            vote_no:    if hidden { 0 } else { paper.vote_no },
+            status:     paper.status,
+            policy:     paper.policy,
+            escrow:     paper.escrow,
+            deadline:   paper.deadline,
+            submitter:  paper.submitter,
+            round:      paper.round,
+            scores:     if hidden {
+                            paper.scores.into_iter().map(|score| CriterionScore::new(score.criterion)).collect()
+                        } else {
+                            paper.scores
+                        },
+            meta_review: paper.meta_review,
+            appeal:     paper.appeal,
+            retraction: paper.retraction,
+            errata:     paper.errata,
+            bibliography: paper.bibliography,
+            published_at: paper.published_at,
+            citation_count: paper.citation_count,
+        }
     }
 }